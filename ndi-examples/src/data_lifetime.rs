@@ -1,12 +1,12 @@
 use std::thread;
 
-fn get_frame(source: &ndi::Source) -> ndi::VideoData {
-    let mut recv = ndi::RecvBuilder::new().build().unwrap();
+fn get_frame(ndi: &ndi::Ndi, source: &ndi::Source) -> ndi::VideoData {
+    let mut recv = ndi::RecvBuilder::new().build(ndi).unwrap();
     recv.connect(source);
 
     let mut video_data = None;
     loop {
-        let response = recv.capture_video(&mut video_data, 1000);
+        let response = recv.capture_video(&mut video_data, 1000).unwrap();
         if response == ndi::FrameType::Video {
             break;
         }
@@ -16,18 +16,14 @@ fn get_frame(source: &ndi::Source) -> ndi::VideoData {
 }
 
 fn main() {
-    ndi::initialize().unwrap();
+    let ndi = ndi::initialize().unwrap();
 
-    let find = ndi::FindBuilder::new().build().unwrap();
-    let sources = find.current_sources(1000).unwrap();
+    let find = ndi::FindBuilder::new().build(&ndi).unwrap();
+    let sources = find.current_sources(1000);
 
-    let frame = get_frame(&sources[0]);
+    let frame = get_frame(&ndi, &sources[0]);
 
     thread::sleep(std::time::Duration::from_millis(1000));
 
     println!("Frame received: {}x{}", frame.width(), frame.height());
-
-    unsafe {
-        ndi::cleanup();
-    }
 }
@@ -5,12 +5,12 @@ use std::{
 };
 
 fn main() {
-    ndi::initialize().unwrap();
-    let find = ndi::FindBuilder::new().build().unwrap();
+    let ndi = ndi::initialize().unwrap();
+    let find = ndi::FindBuilder::new().build(&ndi).unwrap();
 
-    let sources = find.current_sources(1000).unwrap();
+    let sources = find.current_sources(1000);
 
-    let mut recv = ndi::RecvBuilder::new().build().unwrap();
+    let mut recv = ndi::RecvBuilder::new().build(&ndi).unwrap();
     println!("Connecting to the first source: {}", sources[0].get_name());
     recv.connect(&sources[0]);
 
@@ -24,7 +24,7 @@ fn main() {
         let start = Instant::now();
         while Instant::now().duration_since(start).as_millis() < 10000 {
             let mut video_data = None;
-            let response = video_arc.capture_video(&mut video_data, 1000);
+            let response = video_arc.capture_video(&mut video_data, 1000).unwrap();
             if response == ndi::FrameType::Video {
                 if let Some(video) = video_data {
                     video_tx.send(video).unwrap()
@@ -40,7 +40,7 @@ fn main() {
         let start = Instant::now();
         while Instant::now().duration_since(start).as_millis() < 10000 {
             let mut audio_data = None;
-            let response = recv.capture_audio(&mut audio_data, 1000);
+            let response = recv.capture_audio(&mut audio_data, 1000).unwrap();
             if response == ndi::FrameType::Audio {
                 if let Some(audio) = audio_data {
                     audio_tx.send(audio).unwrap()
@@ -66,8 +66,4 @@ fn main() {
             );
         }
     }
-
-    unsafe {
-        ndi::cleanup();
-    }
 }
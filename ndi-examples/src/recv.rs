@@ -2,11 +2,11 @@ use std::io;
 use std::time::Instant;
 
 fn main() {
-    ndi::initialize().unwrap();
+    let ndi = ndi::initialize().unwrap();
 
-    let find = ndi::Find::new().unwrap();
+    let find = ndi::Find::new(&ndi).unwrap();
     println!("Looking for sources");
-    let sources = find.current_sources(1000).unwrap();
+    let sources = find.current_sources(1000);
 
     if sources.len() == 0 {
         panic!("No sources found");
@@ -25,7 +25,7 @@ fn main() {
     let recv_builder = ndi::RecvBuilder::new()
         .color_format(ndi::RecvColorFormat::RGBX_RGBA)
         .ndi_recv_name("ndi-rs".to_string());
-    let mut recv = recv_builder.build().unwrap();
+    let mut recv = recv_builder.build(&ndi).unwrap();
     recv.connect(&sources[i]);
 
     let name = sources[i].get_name();
@@ -33,55 +33,42 @@ fn main() {
 
     let start = Instant::now();
     while Instant::now().duration_since(start).as_secs() < 5 {
-        let mut video_data = None;
-        let mut audio_data = None;
-        let mut meta_data = None;
-        let response = recv.capture_all(&mut video_data, &mut audio_data, &mut meta_data, 1000);
+        let frame = recv.capture(1000).unwrap();
 
         let (total, dropped) = recv.get_performance();
         println!("total:\n {}dropped:\n {}", total, dropped);
 
-        match response {
-            ndi::FrameType::None => println!("Nothing"),
-            ndi::FrameType::Video => {
-                let video_data = video_data.expect("Failed to get video data from capture");
+        match frame {
+            ndi::Frame::Timeout => println!("Nothing"),
+            ndi::Frame::Video(video_data) => {
                 println!(
                     "Got video data: {}x{} {:?}",
                     video_data.width(),
                     video_data.height(),
-                    video_data.four_cc()
+                    video_data.four_cc().unwrap()
                 );
             }
-            ndi::FrameType::Audio => {
-                let audio_data = audio_data.expect("Failed to get audio data from capture");
+            ndi::Frame::Audio(audio_data) => {
                 println!(
                     "Got audio data. Channels: {}, Samples: {}, Stride: {}",
                     audio_data.no_channels(),
                     audio_data.no_samples(),
-                    audio_data.channel_stride_in_bytes()
+                    audio_data.channel_stride_in_bytes().unwrap()
                 );
             }
-            ndi::FrameType::StatusChange => {
+            ndi::Frame::StatusChange => {
                 println!("Status change.")
             }
-            ndi::FrameType::ErrorFrame => {
-                println!("Error")
-            }
-            ndi::FrameType::Metadata => {
-                let meta_data = meta_data.expect("Failed to get meta data from capture");
+            ndi::Frame::Metadata(meta_data) => {
                 println!("Got metadata. {:?}", meta_data.length())
             }
         }
     }
 
     let meta_str = "Hello World".to_owned();
-    let meta = ndi::MetaData::new(0, 0, meta_str);
+    let meta = ndi::MetaData::new(0, 0, meta_str).unwrap();
 
     println!("{}", meta.data());
 
     println!("Done");
-
-    unsafe {
-        ndi::cleanup();
-    }
 }
@@ -1,11 +1,9 @@
-use std::iter::FromIterator;
-
 fn main() {
-    ndi::initialize().unwrap();
+    let ndi = ndi::initialize().unwrap();
 
     let send = ndi::SendBuilder::new()
         .ndi_name("MySender".to_string())
-        .build()
+        .build(&ndi)
         .unwrap();
     let sender_name = "MySender".to_string();
 
@@ -23,28 +21,19 @@ fn main() {
         };
     }
 
-    let video_data_send = ndi::VideoData::from_buffer(
-        1920,
-        1080,
-        ndi::FourCCVideoType::RGBA,
-        30,
-        1,
-        ndi::FrameFormatType::Progressive,
-        0,
-        0,
-        4 * 1920,
-        None,
-        buf.as_mut_slice(),
-    );
+    let video_data_send = ndi::VideoFrame::new(1920, 1080, ndi::FourCCVideoType::RGBA, &buf)
+        .unwrap()
+        .frame_rate(30, 1)
+        .timecode(0);
 
     println!("Made video data");
 
-    let find = ndi::FindBuilder::new().build().unwrap();
-    let sources = find.current_sources(1000).unwrap();
+    let find = ndi::FindBuilder::new().build(&ndi).unwrap();
+    let sources = find.current_sources(1000);
 
     let mut recv = ndi::RecvBuilder::new()
         .color_format(ndi::RecvColorFormat::RGBX_RGBA)
-        .build()
+        .build(&ndi)
         .unwrap();
 
     let mut idx = 0;
@@ -63,7 +52,7 @@ fn main() {
     let mut video_data = None;
     loop {
         send.send_video(&video_data_send);
-        let res = recv.capture_video(&mut video_data, 1000);
+        let res = recv.capture_video(&mut video_data, 1000).unwrap();
         if res == ndi::FrameType::Video {
             break;
         }
@@ -75,20 +64,15 @@ fn main() {
         "Got video data: {}x{} {:?} {} {:?} {:?} {}",
         frame.width(),
         frame.height(),
-        frame.four_cc(),
+        frame.four_cc().unwrap(),
         frame.frame_rate(),
-        frame.frame_format_type(),
-        frame.data_size_in_bytes(),
+        frame.frame_format_type().unwrap(),
+        frame.line_stride_in_bytes(),
         frame.timecode()
     );
 
     // save result to png, same as save_recv example
-    let frame_vec = unsafe {
-        assert!(!frame.p_data().is_null());
-        let size = frame.height() * frame.line_stride_in_bytes().unwrap();
-        std::slice::from_raw_parts(frame.p_data(), size as _)
-    };
-    let frame_vec = Vec::from_iter(frame_vec.to_owned());
+    let frame_vec = frame.data().to_vec();
     let buf = image::ImageBuffer::<image::Rgba<u8>, Vec<_>>::from_vec(
         frame.width(),
         frame.height(),
@@ -99,9 +83,5 @@ fn main() {
 
     buf.save("save_recv.png").unwrap();
 
-    unsafe {
-        ndi::cleanup();
-    }
-
     println!("Done");
 }
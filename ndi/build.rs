@@ -56,6 +56,11 @@ fn lin_link_and_load() {
 }
 
 fn main() {
+    // the runtime is loaded when the program starts instead, see `ndi::load()`
+    if env::var_os("CARGO_FEATURE_DYNAMIC").is_some() {
+        return;
+    }
+
     let os = env::var("CARGO_CFG_TARGET_OS").unwrap();
     match os.as_str() {
         "windows" => win_link_and_load(),
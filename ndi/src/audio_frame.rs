@@ -0,0 +1,249 @@
+use super::*;
+use std::{convert::TryFrom, marker::PhantomData, mem::size_of};
+
+/// An audio frame that can be sent with [`Send::send_audio()`]
+///
+/// This is implemented for frames created for sending ([`AudioFrame`], [`OwnedAudioFrame`])
+/// as well as for received frames ([`AudioData`]), which can be forwarded without copying them.
+pub trait AsAudioFrame {
+    /// Borrow this frame as an [`AudioFrame`]
+    fn as_audio_frame(&self) -> AudioFrame<'_>;
+}
+
+fn frame_binding(
+    sample_rate: u32,
+    no_channels: u32,
+    no_samples: u32,
+    channel_stride: usize,
+    data: &[f32],
+) -> Result<NDIlib_audio_frame_v3_t, Error> {
+    if no_channels == 0 {
+        return Err(Error::OutOfRange(no_channels as _, "no_channels"));
+    }
+    if channel_stride < no_samples as usize {
+        return Err(Error::OutOfRange(channel_stride as _, "channel_stride"));
+    }
+
+    let required = (no_channels as usize - 1) * channel_stride + no_samples as usize;
+    if data.len() < required {
+        return Err(Error::InvalidBufferSize(
+            data.len(),
+            "(no_channels - 1) * channel_stride + no_samples",
+        ));
+    }
+
+    let convert =
+        |value: usize, name| i32::try_from(value).map_err(|_| Error::OutOfRange(value as _, name));
+
+    Ok(NDIlib_audio_frame_v3_t {
+        sample_rate: convert(sample_rate as _, "sample_rate")?,
+        no_channels: convert(no_channels as _, "no_channels")?,
+        no_samples: convert(no_samples as _, "no_samples")?,
+        timecode: NDIlib_send_timecode_synthesize,
+        FourCC: FourCCAudioType::FLTP as _,
+        p_data: data.as_ptr() as _,
+        __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
+            channel_stride_in_bytes: convert(channel_stride * size_of::<f32>(), "channel_stride")?,
+        },
+        p_metadata: null(),
+        timestamp: 0,
+    })
+}
+
+/// The number of samples per channel in a packed planar buffer of `len` samples
+fn planar_no_samples(len: usize, no_channels: u32) -> Result<u32, Error> {
+    let no_channels = no_channels as usize;
+    if no_channels == 0 || len / no_channels * no_channels != len {
+        return Err(Error::InvalidBufferSize(len, "a multiple of no_channels"));
+    }
+
+    u32::try_from(len / no_channels)
+        .map_err(|_| Error::InvalidBufferSize(len, "u32::MAX samples per channel"))
+}
+
+/// An audio frame of planar floating point samples borrowing its data, for sending
+///
+/// The samples of each channel are stored together, one channel after the other.
+/// The -1.0 to +1.0 range corresponds to the professional audio reference level of +4dBU.
+/// Use [`OwnedAudioFrame`] if the frame should own its samples instead.
+#[derive(Clone, Copy)]
+pub struct AudioFrame<'a> {
+    p_instance: NDIlib_audio_frame_v3_t,
+    _data: PhantomData<&'a [f32]>,
+}
+
+unsafe impl core::marker::Send for AudioFrame<'_> {}
+unsafe impl core::marker::Sync for AudioFrame<'_> {}
+
+impl<'a> AudioFrame<'a> {
+    /// Create a frame from `data` holding `no_channels` channels one after the other
+    ///
+    /// The number of samples per channel is `data.len() / no_channels`.
+    pub fn new(sample_rate: u32, no_channels: u32, data: &'a [f32]) -> Result<Self, Error> {
+        let no_samples = planar_no_samples(data.len(), no_channels)?;
+        Self::with_channel_stride(sample_rate, no_channels, no_samples, no_samples as _, data)
+    }
+
+    /// Create a frame from `data` where each channel starts `channel_stride` samples after the previous one
+    pub fn with_channel_stride(
+        sample_rate: u32,
+        no_channels: u32,
+        no_samples: u32,
+        channel_stride: usize,
+        data: &'a [f32],
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            p_instance: frame_binding(sample_rate, no_channels, no_samples, channel_stride, data)?,
+            _data: PhantomData,
+        })
+    }
+
+    pub(crate) fn from_binding(p_instance: NDIlib_audio_frame_v3_t) -> Self {
+        Self {
+            p_instance,
+            _data: PhantomData,
+        }
+    }
+
+    pub(crate) fn as_binding(&self) -> &NDIlib_audio_frame_v3_t {
+        &self.p_instance
+    }
+
+    /// Set the timecode of this frame in 100 ns intervals
+    ///
+    /// default: [`NDIlib_send_timecode_synthesize`], which has the SDK generate it
+    pub fn timecode(mut self, timecode: i64) -> Self {
+        self.p_instance.timecode = timecode;
+        self
+    }
+
+    /// Set the per frame metadata, which should be XML
+    pub fn metadata(mut self, metadata: &'a CStr) -> Self {
+        self.p_instance.p_metadata = metadata.as_ptr();
+        self
+    }
+}
+
+impl AsAudioFrame for AudioFrame<'_> {
+    fn as_audio_frame(&self) -> AudioFrame<'_> {
+        *self
+    }
+}
+
+/// An audio frame of planar floating point samples owning its data and metadata, for sending
+///
+/// See [`AudioFrame`] for the layout of the samples.
+pub struct OwnedAudioFrame {
+    p_instance: NDIlib_audio_frame_v3_t,
+    data: Vec<f32>,
+    metadata: Option<CString>,
+}
+
+unsafe impl core::marker::Send for OwnedAudioFrame {}
+unsafe impl core::marker::Sync for OwnedAudioFrame {}
+
+impl OwnedAudioFrame {
+    /// Create a frame from `data` holding `no_channels` channels one after the other
+    ///
+    /// The number of samples per channel is `data.len() / no_channels`.
+    pub fn new(sample_rate: u32, no_channels: u32, data: Vec<f32>) -> Result<Self, Error> {
+        let no_samples = planar_no_samples(data.len(), no_channels)?;
+        Self::with_channel_stride(sample_rate, no_channels, no_samples, no_samples as _, data)
+    }
+
+    /// Create a frame by copying the samples of each channel
+    ///
+    /// All channels must have the same number of samples.
+    pub fn from_channels(sample_rate: u32, channels: &[&[f32]]) -> Result<Self, Error> {
+        let no_samples = channels.first().map_or(0, |c| c.len());
+        if let Some(channel) = channels.iter().find(|c| c.len() != no_samples) {
+            return Err(Error::InvalidBufferSize(
+                channel.len(),
+                "the same number of samples in every channel",
+            ));
+        }
+
+        let no_channels = u32::try_from(channels.len())
+            .map_err(|_| Error::OutOfRange(channels.len() as _, "no_channels"))?;
+        Self::new(sample_rate, no_channels, channels.concat())
+    }
+
+    /// Create a frame from `data` where each channel starts `channel_stride` samples after the previous one
+    pub fn with_channel_stride(
+        sample_rate: u32,
+        no_channels: u32,
+        no_samples: u32,
+        channel_stride: usize,
+        data: Vec<f32>,
+    ) -> Result<Self, Error> {
+        // the heap allocation of `data` does not move along with the frame
+        Ok(Self {
+            p_instance: frame_binding(sample_rate, no_channels, no_samples, channel_stride, &data)?,
+            data,
+            metadata: None,
+        })
+    }
+
+    /// Set the timecode of this frame in 100 ns intervals
+    ///
+    /// default: [`NDIlib_send_timecode_synthesize`], which has the SDK generate it
+    pub fn timecode(mut self, timecode: i64) -> Self {
+        self.p_instance.timecode = timecode;
+        self
+    }
+
+    /// Set the per frame metadata, which should be XML
+    pub fn metadata(mut self, metadata: String) -> Result<Self, Error> {
+        let metadata = CString::new(metadata).map_err(|_| Error::InvalidString("metadata"))?;
+        self.p_instance.p_metadata = metadata.as_ptr();
+        self.metadata = Some(metadata);
+        Ok(self)
+    }
+
+    /// The samples of the frame
+    pub fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// The samples of the frame, to write the next frame into them
+    pub fn data_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+
+    /// Take back the samples of the frame
+    pub fn into_data(self) -> Vec<f32> {
+        self.data
+    }
+}
+
+impl AsAudioFrame for OwnedAudioFrame {
+    fn as_audio_frame(&self) -> AudioFrame<'_> {
+        AudioFrame::from_binding(self.p_instance)
+    }
+}
+
+impl AsAudioFrame for AudioData {
+    fn as_audio_frame(&self) -> AudioFrame<'_> {
+        AudioFrame::from_binding(self.p_instance)
+    }
+}
+
+#[test]
+fn audio_frame_layout() {
+    let data = [0.0; 2 * 480];
+    let frame = AudioFrame::new(48000, 2, &data).unwrap();
+    assert_eq!(frame.as_binding().no_samples, 480);
+    assert_eq!(
+        unsafe { frame.as_binding().__bindgen_anon_1.channel_stride_in_bytes },
+        480 * 4
+    );
+    assert!(AudioFrame::new(48000, 3, &data[..479]).is_err());
+    assert!(AudioFrame::with_channel_stride(48000, 2, 400, 600, &data).is_err());
+
+    let left = [0.5; 4];
+    let right = [-0.5; 4];
+    let frame = OwnedAudioFrame::from_channels(48000, &[&left, &right]).unwrap();
+    assert_eq!(frame.data(), &[0.5, 0.5, 0.5, 0.5, -0.5, -0.5, -0.5, -0.5]);
+    assert_eq!(frame.as_audio_frame().as_binding().no_channels, 2);
+    assert!(OwnedAudioFrame::from_channels(48000, &[&left, &right[..3]]).is_err());
+}
@@ -0,0 +1,222 @@
+use super::*;
+use std::fmt::Write;
+
+/// Builder for the `<ndi_capabilities/>` connection metadata a sender announces
+///
+/// Other NDI tools use this to show a link to the web control interface of the
+/// sender and to know which remote controls (PTZ, recording etc...) it supports.
+/// Add the result to a [`Send`] with [`Send::add_connection_metadata()`]
+/// so it is sent to every receiver that connects.
+#[derive(Debug, Clone, Default)]
+pub struct CapabilitiesBuilder {
+    web_control: Option<String>,
+    ptz: Option<bool>,
+    pan_tilt: Option<bool>,
+    zoom: Option<bool>,
+    iris: Option<bool>,
+    white_balance: Option<bool>,
+    exposure: Option<bool>,
+    record: Option<bool>,
+}
+
+impl CapabilitiesBuilder {
+    /// Create new builder instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The URL of the web control interface of this sender.
+    ///
+    /// `%IP%` in the URL is replaced with the IP address of the sender by the receiving side,
+    /// for instance `"http://%IP%/settings"`
+    pub fn web_control(mut self, url: String) -> Self {
+        self.web_control = Some(url);
+        self
+    }
+
+    /// Whether this sender can be controlled as a PTZ camera
+    pub fn ptz(mut self, ptz: bool) -> Self {
+        self.ptz = Some(ptz);
+        self
+    }
+
+    /// Whether this sender supports pan and tilt controls
+    pub fn pan_tilt(mut self, pan_tilt: bool) -> Self {
+        self.pan_tilt = Some(pan_tilt);
+        self
+    }
+
+    /// Whether this sender supports zoom controls
+    pub fn zoom(mut self, zoom: bool) -> Self {
+        self.zoom = Some(zoom);
+        self
+    }
+
+    /// Whether this sender supports iris controls
+    pub fn iris(mut self, iris: bool) -> Self {
+        self.iris = Some(iris);
+        self
+    }
+
+    /// Whether this sender supports white balance controls
+    pub fn white_balance(mut self, white_balance: bool) -> Self {
+        self.white_balance = Some(white_balance);
+        self
+    }
+
+    /// Whether this sender supports exposure controls
+    pub fn exposure(mut self, exposure: bool) -> Self {
+        self.exposure = Some(exposure);
+        self
+    }
+
+    /// Whether this sender supports recording controls
+    pub fn record(mut self, record: bool) -> Self {
+        self.record = Some(record);
+        self
+    }
+
+    /// Build the `<ndi_capabilities/>` metadata frame
+    pub fn build(self) -> Result<MetaData, Error> {
+        let mut xml = String::from("<ndi_capabilities");
+        push_attribute(&mut xml, "web_control", self.web_control.as_deref());
+        push_bool_attribute(&mut xml, "ntk_ptz", self.ptz);
+        push_bool_attribute(&mut xml, "ntk_pan_tilt", self.pan_tilt);
+        push_bool_attribute(&mut xml, "ntk_zoom", self.zoom);
+        push_bool_attribute(&mut xml, "ntk_iris", self.iris);
+        push_bool_attribute(&mut xml, "ntk_white_balance", self.white_balance);
+        push_bool_attribute(&mut xml, "ntk_exposure", self.exposure);
+        push_bool_attribute(&mut xml, "ntk_record", self.record);
+        xml.push_str("/>");
+
+        MetaData::new(0, 0, xml)
+    }
+}
+
+/// Builder for the `<ndi_product/>` connection metadata a sender announces
+///
+/// This describes the product behind the sender to other NDI tools.
+/// Add the result to a [`Send`] with [`Send::add_connection_metadata()`]
+/// so it is sent to every receiver that connects.
+#[derive(Debug, Clone, Default)]
+pub struct ProductBuilder {
+    long_name: Option<String>,
+    short_name: Option<String>,
+    manufacturer: Option<String>,
+    version: Option<String>,
+    session: Option<String>,
+    model_name: Option<String>,
+    serial: Option<String>,
+}
+
+impl ProductBuilder {
+    /// Create new builder instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The full name of the product
+    pub fn long_name(mut self, long_name: String) -> Self {
+        self.long_name = Some(long_name);
+        self
+    }
+
+    /// An abbreviated name of the product
+    pub fn short_name(mut self, short_name: String) -> Self {
+        self.short_name = Some(short_name);
+        self
+    }
+
+    /// The manufacturer of the product
+    pub fn manufacturer(mut self, manufacturer: String) -> Self {
+        self.manufacturer = Some(manufacturer);
+        self
+    }
+
+    /// The version of the product, for instance `"1.000.000"`
+    pub fn version(mut self, version: String) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// The session name, for instance `"default"`
+    pub fn session(mut self, session: String) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// The model name of the product
+    pub fn model_name(mut self, model_name: String) -> Self {
+        self.model_name = Some(model_name);
+        self
+    }
+
+    /// The serial number of the product
+    pub fn serial(mut self, serial: String) -> Self {
+        self.serial = Some(serial);
+        self
+    }
+
+    /// Build the `<ndi_product/>` metadata frame
+    pub fn build(self) -> Result<MetaData, Error> {
+        let mut xml = String::from("<ndi_product");
+        push_attribute(&mut xml, "long_name", self.long_name.as_deref());
+        push_attribute(&mut xml, "short_name", self.short_name.as_deref());
+        push_attribute(&mut xml, "manufacturer", self.manufacturer.as_deref());
+        push_attribute(&mut xml, "version", self.version.as_deref());
+        push_attribute(&mut xml, "session", self.session.as_deref());
+        push_attribute(&mut xml, "model_name", self.model_name.as_deref());
+        push_attribute(&mut xml, "serial", self.serial.as_deref());
+        xml.push_str("/>");
+
+        MetaData::new(0, 0, xml)
+    }
+}
+
+fn push_attribute(xml: &mut String, name: &str, value: Option<&str>) {
+    let value = match value {
+        Some(value) => value,
+        None => return,
+    };
+
+    write!(xml, " {}=\"", name).unwrap();
+    for c in value.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\'' => xml.push_str("&apos;"),
+            c => xml.push(c),
+        }
+    }
+    xml.push('"');
+}
+
+fn push_bool_attribute(xml: &mut String, name: &str, value: Option<bool>) {
+    push_attribute(xml, name, value.map(|b| if b { "true" } else { "false" }));
+}
+
+#[test]
+fn capabilities_xml() {
+    let capabilities = CapabilitiesBuilder::new()
+        .web_control("http://%IP%/?a=1&b=2".to_string())
+        .ptz(true)
+        .record(false)
+        .build()
+        .unwrap();
+    assert_eq!(
+        capabilities.data(),
+        r#"<ndi_capabilities web_control="http://%IP%/?a=1&amp;b=2" ntk_ptz="true" ntk_record="false"/>"#
+    );
+
+    let product = ProductBuilder::new()
+        .long_name("My \"Product\"".to_string())
+        .version("1.000.000".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(
+        product.data(),
+        r#"<ndi_product long_name="My &quot;Product&quot;" version="1.000.000"/>"#
+    );
+}
@@ -0,0 +1,602 @@
+use super::*;
+
+/// The matrix between RGB and YUV (Y'CbCr) of a [`ColorSpace`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorMatrix {
+    /// ITU-R BT.601, used by NDI for SD resolutions
+    BT601,
+    /// ITU-R BT.709, used by NDI for HD resolutions
+    BT709,
+    /// ITU-R BT.2020 (non-constant luminance), used by NDI for UHD resolutions
+    BT2020,
+}
+
+impl ColorMatrix {
+    /// The luma weights of red and blue, `(Kr, Kb)`
+    fn coefficients(self) -> (f32, f32) {
+        match self {
+            ColorMatrix::BT601 => (0.299, 0.114),
+            ColorMatrix::BT709 => (0.2126, 0.0722),
+            ColorMatrix::BT2020 => (0.2627, 0.0593),
+        }
+    }
+
+    fn to_rgb(self, [y, cb, cr]: [f32; 3]) -> [f32; 3] {
+        let (kr, kb) = self.coefficients();
+        let kg = 1.0 - kr - kb;
+        let r = y + 2.0 * (1.0 - kr) * cr;
+        let b = y + 2.0 * (1.0 - kb) * cb;
+        let g = (y - kr * r - kb * b) / kg;
+        [r, g, b]
+    }
+
+    fn to_yuv(self, [r, g, b]: [f32; 3]) -> [f32; 3] {
+        let (kr, kb) = self.coefficients();
+        let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+        [
+            y,
+            (b - y) / (2.0 * (1.0 - kb)),
+            (r - y) / (2.0 * (1.0 - kr)),
+        ]
+    }
+}
+
+/// The range of the YUV samples of a [`ColorSpace`]
+///
+/// RGB and alpha samples always use the full range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorRange {
+    /// Luma from 16 to 235 and chroma from 16 to 240, scaled up for 16 bit samples
+    Limited,
+    /// The full range of the data type
+    Full,
+}
+
+/// How YUV samples relate to RGB, see [`FourCCVideoType`] for what NDI uses
+///
+/// default: BT.709, limited range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorSpace {
+    /// The matrix between RGB and YUV
+    pub matrix: ColorMatrix,
+    /// The range of the YUV samples
+    pub range: ColorRange,
+}
+
+impl ColorSpace {
+    /// Create a color space from a matrix and a range
+    pub fn new(matrix: ColorMatrix, range: ColorRange) -> Self {
+        Self { matrix, range }
+    }
+
+    /// The limited range color space NDI uses for frames of this resolution
+    pub fn for_resolution(width: u32, height: u32) -> Self {
+        let matrix = if width > 1920 || height > 1080 {
+            ColorMatrix::BT2020
+        } else if width > 720 || height > 576 {
+            ColorMatrix::BT709
+        } else {
+            ColorMatrix::BT601
+        };
+        Self::new(matrix, ColorRange::Limited)
+    }
+}
+
+impl Default for ColorSpace {
+    fn default() -> Self {
+        Self::new(ColorMatrix::BT709, ColorRange::Limited)
+    }
+}
+
+/// A packed RGB layout to convert frames to and from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RgbFormat {
+    /// 8 bit red, green, blue, alpha
+    RGBA8,
+    /// 8 bit blue, green, red, alpha
+    BGRA8,
+    /// 16 bit red, green, blue, alpha, in native endianness
+    RGBA16,
+}
+
+impl RgbFormat {
+    /// The number of bytes of a single pixel
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            RgbFormat::RGBA8 | RgbFormat::BGRA8 => 4,
+            RgbFormat::RGBA16 => 8,
+        }
+    }
+
+    fn read(self, data: &[u8], pixel: usize) -> [f32; 4] {
+        let p = &data[pixel * self.bytes_per_pixel()..];
+        let n = |v: u8| v as f32 / 255.0;
+        match self {
+            RgbFormat::RGBA8 => [n(p[0]), n(p[1]), n(p[2]), n(p[3])],
+            RgbFormat::BGRA8 => [n(p[2]), n(p[1]), n(p[0]), n(p[3])],
+            RgbFormat::RGBA16 => {
+                let n = |i| read16(p, i) / 65535.0;
+                [n(0), n(1), n(2), n(3)]
+            }
+        }
+    }
+
+    fn write(self, data: &mut [u8], pixel: usize, [r, g, b, a]: [f32; 4]) {
+        let p = &mut data[pixel * self.bytes_per_pixel()..];
+        let q = |v| quantize(v, 255.0) as u8;
+        match self {
+            RgbFormat::RGBA8 => p[..4].copy_from_slice(&[q(r), q(g), q(b), q(a)]),
+            RgbFormat::BGRA8 => p[..4].copy_from_slice(&[q(b), q(g), q(r), q(a)]),
+            RgbFormat::RGBA16 => {
+                for (i, v) in [r, g, b, a].iter().enumerate() {
+                    write16(p, i, quantize(*v, 65535.0));
+                }
+            }
+        }
+    }
+}
+
+/// The sample value of `v` from 0.0 to 1.0, for samples up to `max`
+fn quantize(v: f32, max: f32) -> u16 {
+    (v * max).round().clamp(0.0, max) as u16
+}
+
+fn read16(data: &[u8], index: usize) -> f32 {
+    u16::from_ne_bytes([data[index * 2], data[index * 2 + 1]]) as f32
+}
+
+fn write16(data: &mut [u8], index: usize, value: u16) {
+    data[index * 2..index * 2 + 2].copy_from_slice(&value.to_ne_bytes());
+}
+
+/// The mapping between YUV sample values and normalized luma and chroma
+#[derive(Clone, Copy)]
+struct Levels {
+    luma_offset: f32,
+    luma_scale: f32,
+    chroma_offset: f32,
+    chroma_scale: f32,
+    max: f32,
+}
+
+impl Levels {
+    fn new(range: ColorRange, bits: u32) -> Self {
+        let scale = (1 << (bits - 8)) as f32;
+        let max = ((1 << bits) - 1) as f32;
+        match range {
+            ColorRange::Limited => Self {
+                luma_offset: 16.0 * scale,
+                luma_scale: 219.0 * scale,
+                chroma_offset: 128.0 * scale,
+                chroma_scale: 224.0 * scale,
+                max,
+            },
+            ColorRange::Full => Self {
+                luma_offset: 0.0,
+                luma_scale: max,
+                chroma_offset: 128.0 * scale,
+                chroma_scale: max,
+                max,
+            },
+        }
+    }
+
+    fn decode(&self, [y, u, v]: [f32; 3]) -> [f32; 3] {
+        [
+            (y - self.luma_offset) / self.luma_scale,
+            (u - self.chroma_offset) / self.chroma_scale,
+            (v - self.chroma_offset) / self.chroma_scale,
+        ]
+    }
+
+    fn encode_luma(&self, y: f32) -> u16 {
+        quantize(
+            (y * self.luma_scale + self.luma_offset) / self.max,
+            self.max,
+        )
+    }
+
+    fn encode_chroma(&self, c: f32) -> u16 {
+        quantize(
+            (c * self.chroma_scale + self.chroma_offset) / self.max,
+            self.max,
+        )
+    }
+}
+
+/// YUV formats need an even width, and an even height if the chroma is subsampled vertically
+pub(crate) fn check_dimensions(
+    four_cc: FourCCVideoType,
+    width: usize,
+    height: usize,
+) -> Result<(), Error> {
+    let (even_width, even_height) = match four_cc {
+        FourCCVideoType::UYVY
+        | FourCCVideoType::UYVA
+        | FourCCVideoType::P216
+        | FourCCVideoType::PA16 => (true, false),
+        FourCCVideoType::YV12 | FourCCVideoType::I420 | FourCCVideoType::NV12 => (true, true),
+        _ => (false, false),
+    };
+
+    if even_width && width % 2 == 1 {
+        return Err(Error::OutOfRange(width as _, "width"));
+    }
+    if even_height && height % 2 == 1 {
+        return Err(Error::OutOfRange(height as _, "height"));
+    }
+    Ok(())
+}
+
+/// Line `y` of the plane of a frame holding `kind`
+fn line<'a>(planes: &[Plane<'a>], kind: PlaneKind, y: usize) -> &'a [u8] {
+    planes
+        .iter()
+        .find(|plane| plane.kind() == kind)
+        .and_then(|plane| plane.line(y as _))
+        .expect("the line stride is checked to fit the planes of the FourCC")
+}
+
+/// Decode line `y` of a frame into normalized RGBA pixels
+fn read_line(
+    four_cc: FourCCVideoType,
+    planes: &[Plane],
+    y: usize,
+    color_space: ColorSpace,
+    pixels: &mut [[f32; 4]],
+) {
+    let matrix = color_space.matrix;
+    let rgb = |levels: &Levels, yuv, a| {
+        let [r, g, b] = matrix.to_rgb(levels.decode(yuv));
+        let clamp = |v: f32| v.clamp(0.0, 1.0);
+        [clamp(r), clamp(g), clamp(b), a]
+    };
+
+    match four_cc {
+        FourCCVideoType::BGRA
+        | FourCCVideoType::BGRX
+        | FourCCVideoType::RGBA
+        | FourCCVideoType::RGBX => {
+            let format = match four_cc {
+                FourCCVideoType::BGRA | FourCCVideoType::BGRX => RgbFormat::BGRA8,
+                _ => RgbFormat::RGBA8,
+            };
+            let opaque = matches!(four_cc, FourCCVideoType::BGRX | FourCCVideoType::RGBX);
+            let packed = line(planes, PlaneKind::Packed, y);
+            for (x, pixel) in pixels.iter_mut().enumerate() {
+                *pixel = format.read(packed, x);
+                if opaque {
+                    pixel[3] = 1.0;
+                }
+            }
+        }
+        FourCCVideoType::UYVY | FourCCVideoType::UYVA => {
+            let levels = Levels::new(color_space.range, 8);
+            let packed = line(planes, PlaneKind::Packed, y);
+            let alpha = match four_cc {
+                FourCCVideoType::UYVA => Some(line(planes, PlaneKind::Alpha, y)),
+                _ => None,
+            };
+            for (x, pixel) in pixels.iter_mut().enumerate() {
+                let macro_pixel = &packed[x / 2 * 4..];
+                let yuv = [
+                    packed[x * 2 + 1] as f32,
+                    macro_pixel[0] as f32,
+                    macro_pixel[2] as f32,
+                ];
+                let a = alpha.map_or(1.0, |alpha| alpha[x] as f32 / 255.0);
+                *pixel = rgb(&levels, yuv, a);
+            }
+        }
+        FourCCVideoType::P216 | FourCCVideoType::PA16 => {
+            let levels = Levels::new(color_space.range, 16);
+            let luma = line(planes, PlaneKind::Y, y);
+            let chroma = line(planes, PlaneKind::UV, y);
+            let alpha = match four_cc {
+                FourCCVideoType::PA16 => Some(line(planes, PlaneKind::Alpha, y)),
+                _ => None,
+            };
+            for (x, pixel) in pixels.iter_mut().enumerate() {
+                let yuv = [
+                    read16(luma, x),
+                    read16(chroma, x / 2 * 2),
+                    read16(chroma, x / 2 * 2 + 1),
+                ];
+                let a = alpha.map_or(1.0, |alpha| read16(alpha, x) / 65535.0);
+                *pixel = rgb(&levels, yuv, a);
+            }
+        }
+        FourCCVideoType::YV12 | FourCCVideoType::I420 => {
+            let levels = Levels::new(color_space.range, 8);
+            let luma = line(planes, PlaneKind::Y, y);
+            let u = line(planes, PlaneKind::U, y / 2);
+            let v = line(planes, PlaneKind::V, y / 2);
+            for (x, pixel) in pixels.iter_mut().enumerate() {
+                let yuv = [luma[x] as f32, u[x / 2] as f32, v[x / 2] as f32];
+                *pixel = rgb(&levels, yuv, 1.0);
+            }
+        }
+        FourCCVideoType::NV12 => {
+            let levels = Levels::new(color_space.range, 8);
+            let luma = line(planes, PlaneKind::Y, y);
+            let chroma = line(planes, PlaneKind::UV, y / 2);
+            for (x, pixel) in pixels.iter_mut().enumerate() {
+                let yuv = [
+                    luma[x] as f32,
+                    chroma[x / 2 * 2] as f32,
+                    chroma[x / 2 * 2 + 1] as f32,
+                ];
+                *pixel = rgb(&levels, yuv, 1.0);
+            }
+        }
+    }
+}
+
+/// Convert a frame to packed RGB, writing `width * height` pixels of `format` into `data`
+///
+/// Returns [`Error::InvalidBufferSize`] if `data` is too small,
+/// and [`Error::InvalidEnum`] for compressed frames.
+pub fn to_rgb_into<F: AsVideoFrame + ?Sized>(
+    frame: &F,
+    format: RgbFormat,
+    color_space: ColorSpace,
+    data: &mut [u8],
+) -> Result<(), Error> {
+    let frame = frame.as_video_frame();
+    let four_cc = frame.four_cc()?;
+    let binding = frame.as_binding();
+    let (width, height) = (binding.xres as usize, binding.yres as usize);
+    check_dimensions(four_cc, width, height)?;
+
+    let line_stride = frame.line_stride_in_bytes(four_cc);
+    if line_stride < four_cc.packed_line_stride(width) {
+        return Err(Error::OutOfRange(line_stride as _, "line_stride_in_bytes"));
+    }
+    if data.len() < width * height * format.bytes_per_pixel() {
+        return Err(Error::InvalidBufferSize(
+            data.len(),
+            "width * height * bytes_per_pixel of the RgbFormat",
+        ));
+    }
+
+    let planes = frame.planes()?;
+    let mut pixels = vec![[0.0; 4]; width];
+    for y in 0..height {
+        read_line(four_cc, &planes, y, color_space, &mut pixels);
+        for (x, pixel) in pixels.iter().enumerate() {
+            format.write(data, y * width + x, *pixel);
+        }
+    }
+    Ok(())
+}
+
+/// Convert a frame to a new buffer of packed RGB pixels in `format`
+///
+/// See [`to_rgb_into()`] for the errors.
+pub fn to_rgb<F: AsVideoFrame + ?Sized>(
+    frame: &F,
+    format: RgbFormat,
+    color_space: ColorSpace,
+) -> Result<Vec<u8>, Error> {
+    let binding = *frame.as_video_frame().as_binding();
+    let mut data =
+        vec![0; binding.xres as usize * binding.yres as usize * format.bytes_per_pixel()];
+    to_rgb_into(frame, format, color_space, &mut data)?;
+    Ok(data)
+}
+
+/// Convert packed RGB pixels in `format` to a new frame in the `four_cc` format, with packed lines
+///
+/// Chroma is averaged over the pixels it is shared by, and the alpha of formats without an alpha
+/// channel is dropped. Returns [`Error::InvalidBufferSize`] if `data` is too small.
+pub fn from_rgb(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    format: RgbFormat,
+    four_cc: FourCCVideoType,
+    color_space: ColorSpace,
+) -> Result<OwnedVideoFrame, Error> {
+    let (w, h) = (width as usize, height as usize);
+    check_dimensions(four_cc, w, h)?;
+    if data.len() < w * h * format.bytes_per_pixel() {
+        return Err(Error::InvalidBufferSize(
+            data.len(),
+            "width * height * bytes_per_pixel of the RgbFormat",
+        ));
+    }
+
+    let line_stride = four_cc.packed_line_stride(w);
+    let mut out = vec![0; four_cc.buffer_size(line_stride, h)];
+    let layouts = four_cc.plane_layouts(w, h, line_stride);
+    let find_layout = |kind| layouts.iter().find(|layout| layout.kind == kind).copied();
+    let layout = |kind| find_layout(kind).expect("the plane is part of the FourCC");
+
+    let pixel = |x: usize, y: usize| format.read(data, y * w + x);
+    let average = |pixels: &[[f32; 4]]| {
+        let n = pixels.len() as f32;
+        let sum = pixels.iter().fold([0.0; 3], |sum, p| {
+            [sum[0] + p[0], sum[1] + p[1], sum[2] + p[2]]
+        });
+        [sum[0] / n, sum[1] / n, sum[2] / n]
+    };
+    let matrix = color_space.matrix;
+    let luma = |p: [f32; 4]| matrix.to_yuv([p[0], p[1], p[2]])[0];
+
+    match four_cc {
+        FourCCVideoType::BGRA
+        | FourCCVideoType::BGRX
+        | FourCCVideoType::RGBA
+        | FourCCVideoType::RGBX => {
+            let target = match four_cc {
+                FourCCVideoType::BGRA | FourCCVideoType::BGRX => RgbFormat::BGRA8,
+                _ => RgbFormat::RGBA8,
+            };
+            let opaque = matches!(four_cc, FourCCVideoType::BGRX | FourCCVideoType::RGBX);
+            for y in 0..h {
+                let line = &mut out[y * line_stride..];
+                for x in 0..w {
+                    let mut p = pixel(x, y);
+                    if opaque {
+                        p[3] = 1.0;
+                    }
+                    target.write(line, x, p);
+                }
+            }
+        }
+        FourCCVideoType::UYVY | FourCCVideoType::UYVA => {
+            let levels = Levels::new(color_space.range, 8);
+            let alpha = find_layout(PlaneKind::Alpha);
+            for y in 0..h {
+                for x in (0..w).step_by(2) {
+                    let (p0, p1) = (pixel(x, y), pixel(x + 1, y));
+                    let [_, u, v] = matrix.to_yuv(average(&[p0, p1]));
+                    let i = y * line_stride + x * 2;
+                    out[i] = levels.encode_chroma(u) as u8;
+                    out[i + 1] = levels.encode_luma(luma(p0)) as u8;
+                    out[i + 2] = levels.encode_chroma(v) as u8;
+                    out[i + 3] = levels.encode_luma(luma(p1)) as u8;
+                    if let Some(alpha) = alpha {
+                        let i = alpha.offset + y * alpha.line_stride + x;
+                        out[i] = quantize(p0[3], 255.0) as u8;
+                        out[i + 1] = quantize(p1[3], 255.0) as u8;
+                    }
+                }
+            }
+        }
+        FourCCVideoType::P216 | FourCCVideoType::PA16 => {
+            let levels = Levels::new(color_space.range, 16);
+            let (chroma, alpha) = (layout(PlaneKind::UV), find_layout(PlaneKind::Alpha));
+            for y in 0..h {
+                for x in (0..w).step_by(2) {
+                    let (p0, p1) = (pixel(x, y), pixel(x + 1, y));
+                    let [_, u, v] = matrix.to_yuv(average(&[p0, p1]));
+                    let line = &mut out[y * line_stride..];
+                    write16(line, x, levels.encode_luma(luma(p0)));
+                    write16(line, x + 1, levels.encode_luma(luma(p1)));
+                    let line = &mut out[chroma.offset + y * chroma.line_stride..];
+                    write16(line, x, levels.encode_chroma(u));
+                    write16(line, x + 1, levels.encode_chroma(v));
+                    if let Some(alpha) = alpha {
+                        let line = &mut out[alpha.offset + y * alpha.line_stride..];
+                        write16(line, x, quantize(p0[3], 65535.0));
+                        write16(line, x + 1, quantize(p1[3], 65535.0));
+                    }
+                }
+            }
+        }
+        FourCCVideoType::YV12 | FourCCVideoType::I420 | FourCCVideoType::NV12 => {
+            let levels = Levels::new(color_space.range, 8);
+            for y in 0..h {
+                for x in 0..w {
+                    out[y * line_stride + x] = levels.encode_luma(luma(pixel(x, y))) as u8;
+                }
+            }
+
+            for y in (0..h).step_by(2) {
+                for x in (0..w).step_by(2) {
+                    let block = [
+                        pixel(x, y),
+                        pixel(x + 1, y),
+                        pixel(x, y + 1),
+                        pixel(x + 1, y + 1),
+                    ];
+                    let [_, u, v] = matrix.to_yuv(average(&block));
+                    let (u, v) = (levels.encode_chroma(u) as u8, levels.encode_chroma(v) as u8);
+                    if four_cc == FourCCVideoType::NV12 {
+                        let chroma = layout(PlaneKind::UV);
+                        let i = chroma.offset + y / 2 * chroma.line_stride + x;
+                        out[i] = u;
+                        out[i + 1] = v;
+                    } else {
+                        let (u_plane, v_plane) = (layout(PlaneKind::U), layout(PlaneKind::V));
+                        out[u_plane.offset + y / 2 * u_plane.line_stride + x / 2] = u;
+                        out[v_plane.offset + y / 2 * v_plane.line_stride + x / 2] = v;
+                    }
+                }
+            }
+        }
+    }
+
+    OwnedVideoFrame::with_line_stride(width, height, four_cc, line_stride as _, out)
+}
+
+#[test]
+fn convert_round_trip() {
+    // white in BT.709 limited range
+    let white = [255u8; 4 * 4 * 2];
+    let frame = from_rgb(
+        &white,
+        4,
+        2,
+        RgbFormat::RGBA8,
+        FourCCVideoType::UYVY,
+        ColorSpace::default(),
+    )
+    .unwrap();
+    assert_eq!(&frame.data()[..4], &[128, 235, 128, 235]);
+
+    // flat colors survive the chroma subsampling of every format
+    let colors = [[200u8, 30, 90, 128], [10, 180, 240, 255]];
+    let rgba: Vec<u8> = (0..4 * 4)
+        .flat_map(|i| colors[i / 8].iter().copied())
+        .collect();
+    let formats = [
+        FourCCVideoType::UYVY,
+        FourCCVideoType::UYVA,
+        FourCCVideoType::P216,
+        FourCCVideoType::PA16,
+        FourCCVideoType::YV12,
+        FourCCVideoType::I420,
+        FourCCVideoType::NV12,
+        FourCCVideoType::BGRA,
+        FourCCVideoType::RGBA,
+    ];
+    for &four_cc in formats.iter() {
+        for &matrix in [ColorMatrix::BT601, ColorMatrix::BT709, ColorMatrix::BT2020].iter() {
+            let color_space = ColorSpace::new(matrix, ColorRange::Full);
+            let frame = from_rgb(&rgba, 4, 4, RgbFormat::RGBA8, four_cc, color_space).unwrap();
+            let back = to_rgb(&frame, RgbFormat::BGRA8, color_space).unwrap();
+            let has_alpha = !matches!(
+                four_cc,
+                FourCCVideoType::UYVY
+                    | FourCCVideoType::P216
+                    | FourCCVideoType::YV12
+                    | FourCCVideoType::I420
+                    | FourCCVideoType::NV12
+            );
+            for (i, (a, b)) in rgba.chunks(4).zip(back.chunks(4)).enumerate() {
+                let alpha = if has_alpha { a[3] } else { 255 };
+                let expected = [a[2], a[1], a[0], alpha];
+                let close = expected
+                    .iter()
+                    .zip(b)
+                    .all(|(e, b)| (*e as i32 - *b as i32).abs() <= 2);
+                assert!(
+                    close,
+                    "{:?} pixel {}: {:?} != {:?}",
+                    four_cc, i, expected, b
+                );
+            }
+        }
+    }
+
+    assert!(from_rgb(
+        &rgba,
+        3,
+        4,
+        RgbFormat::RGBA8,
+        FourCCVideoType::NV12,
+        ColorSpace::default()
+    )
+    .is_err());
+    assert!(from_rgb(
+        &rgba,
+        4,
+        5,
+        RgbFormat::RGBA8,
+        FourCCVideoType::BGRA,
+        ColorSpace::default()
+    )
+    .is_err());
+}
@@ -0,0 +1,372 @@
+use super::*;
+
+/// How a [`Deinterlacer`] turns fields into progressive frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeinterlaceMode {
+    /// Interleave the lines of a field 0 and the field 1 following it into one frame
+    ///
+    /// This outputs a frame for every pair of fields and keeps the full vertical resolution,
+    /// but moving content shows combing.
+    Weave,
+    /// Output a frame for every field, interpolating the lines of the other field
+    ///
+    /// This doubles the frame rate and avoids combing at the cost of vertical resolution.
+    Bob,
+    /// Output a frame for every field, taking the lines of the other field from the previous field
+    /// where the picture is still, and interpolating them where it moves
+    MotionAdaptive,
+}
+
+/// The largest difference of an 8 bit sample between fields that still counts as no motion
+const MOTION_THRESHOLD: u32 = 12;
+
+/// The lines of one plane of a field, without padding
+struct FieldPlane {
+    line_len: usize,
+    data: Vec<u8>,
+}
+
+impl FieldPlane {
+    fn height(&self) -> usize {
+        self.data.len() / self.line_len.max(1)
+    }
+
+    fn line(&self, y: usize) -> &[u8] {
+        &self.data[y * self.line_len..(y + 1) * self.line_len]
+    }
+}
+
+/// One field of a frame, with `yres` holding the height of the field
+struct Field {
+    p_instance: NDIlib_video_frame_v2_t,
+    four_cc: FourCCVideoType,
+    metadata: Option<String>,
+    /// 0 for field 0, whose lines are the even lines of the frame
+    parity: usize,
+    planes: Vec<FieldPlane>,
+}
+
+impl Field {
+    /// Copy the lines of `parity` out of the planes of a frame, every line if `step` is 1
+    fn new(
+        frame: &VideoFrame,
+        four_cc: FourCCVideoType,
+        parity: usize,
+        step: usize,
+    ) -> Result<Self, Error> {
+        let binding = frame.as_binding();
+        let mut planes = Vec::new();
+        for plane in frame.planes()? {
+            let height = plane.height() as usize;
+            if step == 2 && height % 2 == 1 {
+                return Err(Error::OutOfRange(binding.yres as _, "height"));
+            }
+
+            let line_len = (plane.width() * plane.bytes_per_pixel()) as usize;
+            let mut data = Vec::with_capacity(line_len * height / step);
+            for y in (parity % step..height).step_by(step) {
+                let line = plane
+                    .line(y as _)
+                    .ok_or(Error::OutOfRange(binding.yres as _, "line_stride_in_bytes"))?;
+                data.extend_from_slice(line);
+            }
+            planes.push(FieldPlane { line_len, data });
+        }
+
+        // the pixel data is not valid beyond the lifetime of `frame`
+        let mut p_instance = *binding;
+        p_instance.yres /= step as i32;
+        p_instance.p_data = null_mut();
+        p_instance.p_metadata = null();
+
+        let metadata = if binding.p_metadata.is_null() {
+            None
+        } else {
+            let metadata = unsafe { CStr::from_ptr(binding.p_metadata) };
+            Some(metadata.to_string_lossy().to_string())
+        };
+
+        Ok(Self {
+            p_instance,
+            four_cc,
+            metadata,
+            parity,
+            planes,
+        })
+    }
+
+    fn matches(&self, other: &Field) -> bool {
+        self.four_cc == other.four_cc
+            && self.p_instance.xres == other.p_instance.xres
+            && self.p_instance.yres == other.p_instance.yres
+    }
+
+    /// Build a frame of `height` lines, filling line `y` of plane `i` with `fill(i, y, line)`
+    fn frame(
+        &self,
+        height: usize,
+        rate_factor: i32,
+        fill: impl Fn(usize, usize, &mut [u8]),
+    ) -> Result<OwnedVideoFrame, Error> {
+        let width = self.p_instance.xres as usize;
+        let line_stride = self.four_cc.packed_line_stride(width);
+        let mut data = vec![0; self.four_cc.buffer_size(line_stride, height)];
+
+        let layouts = self.four_cc.plane_layouts(width, height, line_stride);
+        for (i, layout) in layouts.iter().enumerate() {
+            let line_len = layout.width * layout.bytes_per_pixel;
+            for y in 0..layout.height {
+                let start = layout.offset + y * layout.line_stride;
+                fill(i, y, &mut data[start..start + line_len]);
+            }
+        }
+
+        let frame = OwnedVideoFrame::new(width as _, height as _, self.four_cc, data)?
+            .frame_rate(
+                self.p_instance.frame_rate_N * rate_factor,
+                self.p_instance.frame_rate_D,
+            )
+            .picture_aspect_ratio(self.p_instance.picture_aspect_ratio)
+            .timecode(self.p_instance.timecode);
+        match &self.metadata {
+            Some(metadata) => frame.metadata(metadata.clone()),
+            None => Ok(frame),
+        }
+    }
+
+    /// Check that the planes of a frame twice the height of this field line up with its planes
+    fn frame_height(&self) -> Result<usize, Error> {
+        let height = self.p_instance.yres as usize * 2;
+        let width = self.p_instance.xres as usize;
+        let line_stride = self.four_cc.packed_line_stride(width);
+        let layouts = self.four_cc.plane_layouts(width, height, line_stride);
+        let aligned = layouts
+            .iter()
+            .zip(&self.planes)
+            .all(|(layout, plane)| layout.height == plane.height() * 2);
+        if aligned {
+            Ok(height)
+        } else {
+            Err(Error::OutOfRange(self.p_instance.yres as _, "height"))
+        }
+    }
+}
+
+fn sample_bits(four_cc: FourCCVideoType) -> u32 {
+    match four_cc {
+        FourCCVideoType::P216 | FourCCVideoType::PA16 => 16,
+        _ => 8,
+    }
+}
+
+/// Write `sample(i)` into every sample `i` of `line`
+fn fill_samples(line: &mut [u8], bits: u32, sample: impl Fn(usize) -> u32) {
+    if bits == 16 {
+        for (i, bytes) in line.chunks_exact_mut(2).enumerate() {
+            bytes.copy_from_slice(&(sample(i) as u16).to_ne_bytes());
+        }
+    } else {
+        for (i, byte) in line.iter_mut().enumerate() {
+            *byte = sample(i) as u8;
+        }
+    }
+}
+
+/// The rounded average of two samples
+fn average(a: u32, b: u32) -> u32 {
+    (a + b + 1) >> 1
+}
+
+fn read_sample(line: &[u8], bits: u32, i: usize) -> u32 {
+    if bits == 16 {
+        u16::from_ne_bytes([line[i * 2], line[i * 2 + 1]]) as u32
+    } else {
+        line[i] as u32
+    }
+}
+
+/// The lines of `field` around line `y` of the other field, in field line indices
+fn neighbours(field: &Field, plane: usize, y: usize) -> (usize, usize) {
+    let parity = field.parity;
+    let above = if y > parity { (y - 1 - parity) / 2 } else { 0 };
+    let below = ((y + 1 - parity) / 2).min(field.planes[plane].height() - 1);
+    (above, below)
+}
+
+/// Turns fields into progressive frames
+///
+/// Frames are pushed in the order they are received, see [`Deinterlacer::push()`].
+/// The output frames keep the FourCC of the input, with packed lines.
+pub struct Deinterlacer {
+    mode: DeinterlaceMode,
+    previous: Option<Field>,
+}
+
+impl Deinterlacer {
+    /// Create a deinterlacer using `mode`
+    pub fn new(mode: DeinterlaceMode) -> Self {
+        Self {
+            mode,
+            previous: None,
+        }
+    }
+
+    /// The mode of the deinterlacer
+    pub fn mode(&self) -> DeinterlaceMode {
+        self.mode
+    }
+
+    /// Push the next received frame, returning the progressive frames completed by it
+    ///
+    /// [`FrameFormatType::Field0`] and [`FrameFormatType::Field1`] frames hold a single field
+    /// half the height of the frame, while [`FrameFormatType::Interleaved`] frames hold both.
+    /// Progressive frames are copied as they are.
+    /// Which and how many frames are returned depends on the [`DeinterlaceMode`],
+    /// for instance [`DeinterlaceMode::Weave`] returns nothing for a field 0.
+    pub fn push<F: AsVideoFrame + ?Sized>(
+        &mut self,
+        frame: &F,
+    ) -> Result<Vec<OwnedVideoFrame>, Error> {
+        let frame = frame.as_video_frame();
+        let four_cc = frame.four_cc()?;
+
+        let fields = match FrameFormatType::try_from(frame.as_binding().frame_format_type)? {
+            FrameFormatType::Progressive => {
+                self.previous = None;
+                let field = Field::new(&frame, four_cc, 0, 1)?;
+                let height = field.p_instance.yres as usize;
+                let progressive = field.frame(height, 1, |i, y, line| {
+                    line.copy_from_slice(field.planes[i].line(y))
+                })?;
+                return Ok(vec![progressive]);
+            }
+            FrameFormatType::Interleaved => vec![
+                Field::new(&frame, four_cc, 0, 2)?,
+                Field::new(&frame, four_cc, 1, 2)?,
+            ],
+            FrameFormatType::Field0 => vec![Field::new(&frame, four_cc, 0, 1)?],
+            FrameFormatType::Field1 => vec![Field::new(&frame, four_cc, 1, 1)?],
+        };
+
+        let mut frames = Vec::new();
+        for field in fields {
+            if let Some(frame) = self.push_field(field)? {
+                frames.push(frame);
+            }
+        }
+        Ok(frames)
+    }
+
+    fn push_field(&mut self, field: Field) -> Result<Option<OwnedVideoFrame>, Error> {
+        let height = field.frame_height()?;
+        let previous = self
+            .previous
+            .take()
+            .filter(|previous| previous.parity != field.parity && previous.matches(&field));
+        let bits = sample_bits(field.four_cc);
+
+        let frame = match self.mode {
+            DeinterlaceMode::Weave => match (field.parity, previous) {
+                (0, _) => {
+                    self.previous = Some(field);
+                    return Ok(None);
+                }
+                (_, Some(first)) => first.frame(height, 1, |i, y, line| {
+                    let source = if y % 2 == 0 { &first } else { &field };
+                    line.copy_from_slice(source.planes[i].line(y / 2))
+                })?,
+                (_, None) => return Ok(None),
+            },
+            DeinterlaceMode::Bob => field.frame(height, 2, |i, y, line| {
+                if y % 2 == field.parity {
+                    line.copy_from_slice(field.planes[i].line(y / 2));
+                } else {
+                    let (above, below) = neighbours(&field, i, y);
+                    let (above, below) = (field.planes[i].line(above), field.planes[i].line(below));
+                    fill_samples(line, bits, |s| {
+                        average(read_sample(above, bits, s), read_sample(below, bits, s))
+                    });
+                }
+            })?,
+            DeinterlaceMode::MotionAdaptive => {
+                let threshold = MOTION_THRESHOLD << (bits - 8);
+                let frame = field.frame(height, 2, |i, y, line| {
+                    if y % 2 == field.parity {
+                        line.copy_from_slice(field.planes[i].line(y / 2));
+                        return;
+                    }
+
+                    let (above, below) = neighbours(&field, i, y);
+                    let (above, below) = (field.planes[i].line(above), field.planes[i].line(below));
+                    let other = previous
+                        .as_ref()
+                        .map(|previous| previous.planes[i].line(y / 2));
+                    fill_samples(line, bits, |s| {
+                        let interpolated =
+                            average(read_sample(above, bits, s), read_sample(below, bits, s));
+                        match other {
+                            Some(other) => {
+                                let woven = read_sample(other, bits, s);
+                                let still =
+                                    (woven as i64 - interpolated as i64).abs() <= threshold as i64;
+                                if still {
+                                    woven
+                                } else {
+                                    interpolated
+                                }
+                            }
+                            None => interpolated,
+                        }
+                    });
+                })?;
+                self.previous = Some(field);
+                frame
+            }
+        };
+        Ok(Some(frame))
+    }
+}
+
+#[test]
+fn deinterlace_fields() {
+    // line y of a 4x4 frame is filled with y * 10
+    let data: Vec<u8> = (0..4u8).flat_map(|y| vec![y * 10; 4 * 4]).collect();
+    let interleaved = VideoFrame::new(4, 4, FourCCVideoType::BGRA, &data)
+        .unwrap()
+        .frame_format_type(FrameFormatType::Interleaved);
+
+    let mut weave = Deinterlacer::new(DeinterlaceMode::Weave);
+    let frames = weave.push(&interleaved).unwrap();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].data(), &data[..]);
+
+    let mut bob = Deinterlacer::new(DeinterlaceMode::Bob);
+    let frames = bob.push(&interleaved).unwrap();
+    assert_eq!(frames.len(), 2);
+    let lines: Vec<u8> = frames[1].data().chunks(16).map(|line| line[0]).collect();
+    assert_eq!(lines, [10, 10, 20, 30]);
+
+    // fields of a still picture are woven back together
+    let (even, odd): (Vec<_>, Vec<_>) = data.chunks(16).enumerate().partition(|(y, _)| y % 2 == 0);
+    let even: Vec<u8> = even
+        .into_iter()
+        .flat_map(|(_, line)| line.to_vec())
+        .collect();
+    let odd: Vec<u8> = odd
+        .into_iter()
+        .flat_map(|(_, line)| line.to_vec())
+        .collect();
+    let field0 = VideoFrame::new(4, 2, FourCCVideoType::BGRA, &even)
+        .unwrap()
+        .frame_format_type(FrameFormatType::Field0);
+    let field1 = VideoFrame::new(4, 2, FourCCVideoType::BGRA, &odd)
+        .unwrap()
+        .frame_format_type(FrameFormatType::Field1);
+
+    assert!(weave.push(&field0).unwrap().is_empty());
+    assert_eq!(weave.push(&field1).unwrap()[0].data(), &data[..]);
+
+    let mut adaptive = Deinterlacer::new(DeinterlaceMode::MotionAdaptive);
+    adaptive.push(&field0).unwrap();
+    assert_eq!(adaptive.push(&field1).unwrap()[0].data(), &data[..]);
+}
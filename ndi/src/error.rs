@@ -1,43 +1,50 @@
 use super::*;
 
-macro_rules! impl_error {
-    ($name:ident) => {
-        impl Display for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                <Self as Debug>::fmt(&self, f)
+/// The error type used in this crate
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// A string parameter contained an interior NUL byte: `(parameter name)`
+    InvalidString(&'static str),
+    /// Failed to convert a c `int` into an `enum`: `(value, enum name)`
+    InvalidEnum(i32, &'static str),
+    /// The SDK failed to create an instance: `(instance type)`
+    CreateFailed(&'static str),
+    /// Waiting for the SDK timed out
+    Timeout,
+    /// The system or the connected source does not support this: `(feature)`
+    NotSupported(&'static str),
+    /// The connection to the source was lost
+    Disconnected,
+    /// A parameter was outside of the range accepted by the SDK: `(value, parameter name)`
+    OutOfRange(f32, &'static str),
+    /// The SDK failed to send a control command to the source: `(command)`
+    CommandFailed(&'static str),
+    /// A sample or pixel buffer does not have the size required by its layout: `(buffer length, expected size)`
+    InvalidBufferSize(usize, &'static str),
+    /// The NDI runtime library could not be loaded: `(reason)`
+    Load(String),
+    /// A source name was not in the form `MACHINE_NAME (NDI_SOURCE_NAME)`: `(name)`
+    InvalidSourceName(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidString(name) => write!(f, "{} contains an interior NUL byte", name),
+            Error::InvalidEnum(value, name) => write!(f, "invalid value {} for {}", value, name),
+            Error::CreateFailed(name) => write!(f, "failed to create an instance of {}", name),
+            Error::Timeout => write!(f, "timed out"),
+            Error::NotSupported(feature) => write!(f, "{} is not supported", feature),
+            Error::Disconnected => write!(f, "the connection to the source was lost"),
+            Error::OutOfRange(value, name) => write!(f, "{} is out of range: {}", name, value),
+            Error::CommandFailed(command) => write!(f, "the {} command failed", command),
+            Error::InvalidBufferSize(len, expected) => {
+                write!(f, "invalid buffer size {}, expected {}", len, expected)
             }
+            Error::Load(reason) => write!(f, "{}", reason),
+            Error::InvalidSourceName(name) => write!(f, "invalid source name {:?}", name),
         }
-
-        impl std::error::Error for $name {}
-    };
+    }
 }
 
-/// The system is not compatible with NDI
-#[derive(Debug)]
-pub struct NotSupported;
-impl_error!(NotSupported);
-
-/// Failed to convert a c `int` into an `enum`
-#[derive(Debug)]
-pub struct InvalidEnum(pub i32, pub &'static str);
-impl_error!(InvalidEnum);
-
-/// Failed to create an instance of Recv
-#[derive(Debug)]
-pub struct RecvCreateError;
-impl_error!(RecvCreateError);
-
-/// Failed to create an instance of Find
-#[derive(Debug)]
-pub struct FindCreateError;
-impl_error!(FindCreateError);
-
-/// Failed to create an instance of Send
-#[derive(Debug)]
-pub struct SendCreateError;
-impl_error!(SendCreateError);
-
-/// Findng the current sources timed out
-#[derive(Debug)]
-pub struct FindSourcesTimeout;
-impl_error!(FindSourcesTimeout);
+impl std::error::Error for Error {}
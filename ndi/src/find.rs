@@ -1,7 +1,7 @@
 use crate::internal::OnDrop;
 
 use super::*;
-use std::{ffi::CString, thread::yield_now, time::Instant};
+use std::{collections::VecDeque, convert::TryFrom, ffi::CString, time::Instant};
 
 /// Builder for [`Find`] struct
 #[derive(Debug, Clone)]
@@ -53,7 +53,7 @@ impl FindBuilder {
     }
 
     /// Build an instance of [`Find`]
-    pub fn build(self) -> Result<Find, FindCreateError> {
+    pub fn build(self, ndi: &Ndi) -> Result<Find, Error> {
         // from default c++ constructor in Processing.NDI.Find.h
         let mut settings = NDIlib_find_create_t {
             show_local_sources: true,
@@ -66,16 +66,16 @@ impl FindBuilder {
         }
 
         if let Some(groups) = self.groups {
-            let cstr = CString::new(groups).unwrap();
+            let cstr = CString::new(groups).map_err(|_| Error::InvalidString("groups"))?;
             settings.p_groups = cstr.into_raw();
         }
 
         if let Some(extra_ips) = self.extra_ips {
-            let cstr = CString::new(extra_ips).unwrap();
+            let cstr = CString::new(extra_ips).map_err(|_| Error::InvalidString("extra_ips"))?;
             settings.p_extra_ips = cstr.into_raw();
         }
 
-        Find::with_settings(settings)
+        Find::with_settings(settings, ndi)
     }
 }
 
@@ -94,58 +94,197 @@ unsafe impl core::marker::Sync for Find {}
 
 impl Find {
     /// Create a new instance with default constructor
-    pub fn new() -> Result<Self, FindCreateError> {
+    pub fn new(ndi: &Ndi) -> Result<Self, Error> {
         let p_instance = unsafe { NDIlib_find_create_v2(null()) };
         if p_instance.is_null() {
-            return Err(FindCreateError);
+            return Err(Error::CreateFailed("Find"));
         };
 
-        let p_instance = Arc::new(OnDrop::new(p_instance, |s| unsafe {
-            NDIlib_find_destroy(s)
-        }));
+        let p_instance =
+            Arc::new(OnDrop::new(p_instance, |s| unsafe { NDIlib_find_destroy(s) }).retain(ndi));
         Ok(Self { p_instance })
     }
 
-    fn with_settings(settings: NDIlib_find_create_t) -> Result<Self, FindCreateError> {
+    fn with_settings(settings: NDIlib_find_create_t, ndi: &Ndi) -> Result<Self, Error> {
         let p_instance = unsafe { NDIlib_find_create_v2(&settings) };
         if p_instance.is_null() {
-            return Err(FindCreateError);
+            return Err(Error::CreateFailed("Find"));
         };
 
-        let p_instance = Arc::new(OnDrop::new(p_instance, |s| unsafe {
-            NDIlib_find_destroy(s)
-        }));
+        let p_instance =
+            Arc::new(OnDrop::new(p_instance, |s| unsafe { NDIlib_find_destroy(s) }).retain(ndi));
         Ok(Self { p_instance })
     }
 
-    /// List current sources
-    pub fn current_sources(&self, timeout_ms: u128) -> Result<Vec<Source>, FindSourcesTimeout> {
-        let mut no_sources = 0;
+    /// List current sources, waiting up to `timeout_ms` for the first ones to be found
+    ///
+    /// Returns an empty list if there are no sources on the network by then.
+    pub fn current_sources(&self, timeout_ms: u128) -> Vec<Source> {
         let start = Instant::now();
-        let p_sources = loop {
-            // timeout if it takes an unreasonable amount of time
-            if start.elapsed().as_millis() > timeout_ms {
-                return Err(FindSourcesTimeout);
+        loop {
+            let sources = self.get_current_sources();
+            let elapsed = start.elapsed().as_millis();
+            if !sources.is_empty() || elapsed >= timeout_ms {
+                return sources;
             }
 
-            let p_sources =
-                unsafe { NDIlib_find_get_current_sources(**self.p_instance, &mut no_sources) };
+            let remaining = u32::try_from(timeout_ms - elapsed).unwrap_or(u32::MAX);
+            self.wait_for_sources(remaining);
+        }
+    }
+
+    /// Wait up to `timeout_ms` for the list of sources to change
+    ///
+    /// Returns `false` if nothing changed before the timeout.
+    pub fn wait_for_sources(&self, timeout_ms: u32) -> bool {
+        unsafe { NDIlib_find_wait_for_sources(**self.p_instance, timeout_ms) }
+    }
 
-            if no_sources != 0 {
-                break p_sources;
-            } else {
-                yield_now();
+    /// Find a source by its full name or by its stream name on any machine, see [`SourceName::refers_to()`]
+    ///
+    /// Waits up to `timeout_ms` for the source to appear, and returns `None` if it does not.
+    pub fn find_by_name(&self, name: &str, timeout_ms: u128) -> Option<Source> {
+        let start = Instant::now();
+        loop {
+            let found = self.get_current_sources().into_iter().find(|source| {
+                source
+                    .source_name()
+                    .is_some_and(|source_name| source_name.refers_to(name))
+            });
+            let elapsed = start.elapsed().as_millis();
+            if found.is_some() || elapsed >= timeout_ms {
+                return found;
             }
-        };
 
+            let remaining = u32::try_from(timeout_ms - elapsed).unwrap_or(u32::MAX);
+            self.wait_for_sources(remaining);
+        }
+    }
+
+    /// List the current sources whose name matches a glob `pattern`, see [`SourceName::matches()`]
+    ///
+    /// Waits for sources like [`Find::current_sources()`].
+    pub fn sources_matching(&self, pattern: &str, timeout_ms: u128) -> Vec<Source> {
+        self.sources_where(timeout_ms, |name| name.matches(pattern))
+    }
+
+    /// List the current sources whose name satisfies `predicate`, such as a regular expression
+    ///
+    /// Waits for sources like [`Find::current_sources()`].
+    /// Sources without a name in the usual form are skipped.
+    pub fn sources_where<F: FnMut(&SourceName) -> bool>(
+        &self,
+        timeout_ms: u128,
+        mut predicate: F,
+    ) -> Vec<Source> {
+        let mut sources = self.current_sources(timeout_ms);
+        sources.retain(|source| source.source_name().is_some_and(|name| predicate(&name)));
+        sources
+    }
+
+    /// Watch the sources on the network for changes, see [`SourceWatcher`]
+    pub fn watch(self) -> SourceWatcher {
+        SourceWatcher::new(self)
+    }
+
+    fn get_current_sources(&self) -> Vec<Source> {
+        let mut no_sources = 0;
+        let p_sources =
+            unsafe { NDIlib_find_get_current_sources(**self.p_instance, &mut no_sources) };
+
+        // copies, as the list is only valid until the next call
         let mut sources: Vec<Source> = vec![];
         for k in 0..no_sources {
-            let parent = SourceParent::Find(Arc::clone(&self.p_instance));
-            sources.push(Source::from_binding(parent, unsafe {
-                *p_sources.offset(k as _)
-            }));
+            sources.push(Source::from_binding(unsafe { &*p_sources.offset(k as _) }));
         }
 
-        Ok(sources)
+        sources
+    }
+}
+
+/// A change to the sources available on the network, reported by a [`SourceWatcher`]
+#[derive(Debug, Clone)]
+pub enum SourceEvent {
+    /// A source was found
+    Added(Source),
+    /// A source is no longer available
+    Removed(Source),
+}
+
+/// Reports sources appearing and disappearing on the network
+///
+/// The watcher blocks on the SDK until the list of sources changes,
+/// then compares it with the previous one by name.
+/// The first changes report all of the sources already known to the [`Find`].
+/// As an iterator, it blocks until the next event and never ends.
+pub struct SourceWatcher {
+    find: Find,
+    sources: Vec<Source>,
+    events: VecDeque<SourceEvent>,
+    started: bool,
+}
+
+impl SourceWatcher {
+    /// Start watching the sources found by `find`
+    pub fn new(find: Find) -> Self {
+        Self {
+            find,
+            sources: vec![],
+            events: VecDeque::new(),
+            started: false,
+        }
+    }
+
+    /// The sources currently known to the watcher
+    pub fn sources(&self) -> &[Source] {
+        &self.sources
+    }
+
+    /// Wait up to `timeout_ms` for the sources to change, and return the changes
+    ///
+    /// Returns an empty list if nothing changed before the timeout.
+    /// Events not yet taken through the iterator are returned first, without waiting.
+    pub fn wait(&mut self, timeout_ms: u32) -> Vec<SourceEvent> {
+        if self.events.is_empty() {
+            self.update(timeout_ms);
+        }
+        self.events.drain(..).collect()
+    }
+
+    fn update(&mut self, timeout_ms: u32) {
+        // the sources found before the watcher started are reported without waiting
+        let found = !self.started && !self.find.get_current_sources().is_empty();
+        self.started = true;
+        if !found {
+            self.find.wait_for_sources(timeout_ms);
+        }
+
+        let sources = self.find.get_current_sources();
+        let names: Vec<String> = sources.iter().map(Source::get_name).collect();
+        let previous: Vec<String> = self.sources.iter().map(Source::get_name).collect();
+
+        for (source, name) in self.sources.iter().zip(&previous) {
+            if !names.contains(name) {
+                self.events.push_back(SourceEvent::Removed(source.clone()));
+            }
+        }
+        for (source, name) in sources.iter().zip(&names) {
+            if !previous.contains(name) {
+                self.events.push_back(SourceEvent::Added(source.clone()));
+            }
+        }
+
+        self.sources = sources;
+    }
+}
+
+impl Iterator for SourceWatcher {
+    type Item = SourceEvent;
+
+    fn next(&mut self) -> Option<SourceEvent> {
+        while self.events.is_empty() {
+            self.update(u32::MAX);
+        }
+        self.events.pop_front()
     }
 }
@@ -0,0 +1,122 @@
+use super::*;
+use std::mem::MaybeUninit;
+
+/// The frame-sync instance together with the receiver it was created from.
+///
+/// The receiver must only be destroyed after the frame-sync has been destroyed,
+/// so the fields are declared in this order.
+pub(crate) struct FrameSyncInstance {
+    pub(crate) p_instance: OnDrop<NDIlib_framesync_instance_t>,
+    _recv: Arc<OnDrop<NDIlib_recv_instance_t>>,
+}
+
+unsafe impl core::marker::Send for FrameSyncInstance {}
+unsafe impl core::marker::Sync for FrameSyncInstance {}
+
+/// A time-base corrector for video and audio received through a [`Recv`]
+///
+/// A frame-sync transforms "push" sources (the sender pushes frames at its own clock rate)
+/// into "pull" sources where the application pulls frames at the rate of its own clock,
+/// for instance the GPU v-sync or the sound card clock.
+/// Video frames are dropped or duplicated using hysteresis and audio is dynamically resampled to
+/// track clocking differences between the sender and the receiver.
+///
+/// Once a [`Recv`] has been bound to a frame-sync you should use the frame-sync to receive video and audio frames.
+/// You can continue to use the underlying receiver for other operations (tally, PTZ, metadata etc...).
+/// The receiver is kept alive until the frame-sync and all frames captured with it are dropped.
+pub struct FrameSync {
+    p_instance: Arc<FrameSyncInstance>,
+}
+
+unsafe impl core::marker::Send for FrameSync {}
+unsafe impl core::marker::Sync for FrameSync {}
+
+impl FrameSync {
+    /// Create a frame-sync bound to `recv`
+    pub fn new(recv: &Recv) -> Result<Self, Error> {
+        let p_instance = unsafe { NDIlib_framesync_create(**recv.p_instance) };
+        if p_instance.is_null() {
+            return Err(Error::CreateFailed("FrameSync"));
+        }
+
+        let p_instance = Arc::new(FrameSyncInstance {
+            p_instance: OnDrop::new(p_instance, |s| unsafe { NDIlib_framesync_destroy(s) }),
+            _recv: Arc::clone(&recv.p_instance),
+        });
+        Ok(Self { p_instance })
+    }
+
+    /// Pull a video frame from the frame-sync queue.
+    ///
+    /// This will always return immediately by using time-base correction, and the same frame can be returned multiple times.
+    /// `field_type` is the frame format that you would prefer, all effort is made to match it.
+    /// For fielded input, the frame-sync attempts to match the fielded input phase with the frame requests
+    /// so that you have the most correct possible field ordering on output.
+    ///
+    /// Returns `None` if no video frame has ever been received.
+    pub fn capture_video(&self, field_type: FrameFormatType) -> Option<VideoData> {
+        let mut video = MaybeUninit::<NDIlib_video_frame_v2_t>::zeroed();
+        unsafe {
+            NDIlib_framesync_capture_video(
+                *self.p_instance.p_instance,
+                video.as_mut_ptr(),
+                field_type as _,
+            )
+        };
+
+        let video = VideoData::from_binding_framesync(Arc::clone(&self.p_instance), unsafe {
+            video.assume_init()
+        });
+
+        if video.p_data().is_null() {
+            None
+        } else {
+            Some(video)
+        }
+    }
+
+    /// Pull `no_samples` audio samples from the frame-sync queue.
+    ///
+    /// This will always return data immediately, inserting silence if no current audio data is present.
+    /// You should call this at the rate that you want audio and it will automatically adapt the incoming audio
+    /// signal to match the rate at which you are calling by using dynamic audio sampling.
+    /// Your requested sample rate, number of channels and number of samples do not need to match the
+    /// incoming signal, all combinations of conversions are supported.
+    pub fn capture_audio(&self, sample_rate: u32, no_channels: u32, no_samples: u32) -> AudioData {
+        let mut audio = MaybeUninit::<NDIlib_audio_frame_v3_t>::zeroed();
+        unsafe {
+            NDIlib_framesync_capture_audio_v2(
+                *self.p_instance.p_instance,
+                audio.as_mut_ptr(),
+                sample_rate as _,
+                no_channels as _,
+                no_samples as _,
+            )
+        };
+
+        AudioData::from_binding_framesync(Arc::clone(&self.p_instance), unsafe {
+            audio.assume_init()
+        })
+    }
+
+    /// The format of the incoming audio as `(sample_rate, no_channels)`
+    ///
+    /// Returns `None` if no audio is currently being received.
+    pub fn audio_format(&self) -> Option<(u32, u32)> {
+        let audio = self.capture_audio(0, 0, 0);
+        if audio.sample_rate() == 0 || audio.no_channels() == 0 {
+            None
+        } else {
+            Some((audio.sample_rate(), audio.no_channels()))
+        }
+    }
+
+    /// The approximate current depth of the audio queue in samples.
+    ///
+    /// This gives you an indication of the number of audio samples you can request.
+    /// The frame-sync is meant to dynamically resample audio to match the rate that you are calling it,
+    /// but this can be useful if you have an inaccurate clock.
+    pub fn audio_queue_depth(&self) -> u32 {
+        unsafe { NDIlib_framesync_audio_queue_depth(*self.p_instance.p_instance) as _ }
+    }
+}
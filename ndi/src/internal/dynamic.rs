@@ -0,0 +1,533 @@
+//! Dispatch of the NDI functions through the `NDIlib_v4` table of a runtime loaded with `dlopen`.
+//!
+//! Every function of the table is shadowed by a wrapper of the same name and signature,
+//! so the rest of the crate does not need to care whether the library was linked or loaded.
+//! Functions which are not part of the table (and which would require linking) are not wrapped.
+
+#![allow(clippy::missing_safety_doc)]
+#![allow(clippy::too_many_arguments)]
+
+#[cfg(target_os = "windows")]
+pub use super::bindings_windows::*;
+
+#[cfg(target_os = "linux")]
+pub use super::bindings_linux::*;
+
+use crate::error::Error;
+use libloading::Library;
+use std::{
+    ffi::{CStr, OsStr},
+    os::raw::{c_char, c_int},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+type ExposureManualV2 = unsafe extern "C" fn(NDIlib_recv_instance_t, f32, f32, f32) -> bool;
+
+struct Runtime {
+    table: &'static NDIlib_v4,
+    // Only part of the table on some platforms so it is looked up by name
+    exposure_manual_v2: Option<ExposureManualV2>,
+    // never dropped, the table points into the library
+    _library: Library,
+}
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+fn library_name() -> &'static OsStr {
+    let name = CStr::from_bytes_with_nul(NDILIB_LIBRARY_NAME).unwrap();
+    OsStr::new(name.to_str().unwrap())
+}
+
+fn open(path: &Path) -> Result<Runtime, Error> {
+    let library = unsafe { Library::new(path) }
+        .map_err(|e| Error::Load(format!("failed to load {}: {}", path.display(), e)))?;
+
+    let table = unsafe {
+        let load = library
+            .get::<unsafe extern "C" fn() -> *const NDIlib_v4>(b"NDIlib_v4_load\0")
+            .map_err(|e| {
+                Error::Load(format!(
+                    "{} is not an NDI v4 runtime: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        load()
+    };
+    if table.is_null() {
+        return Err(Error::Load(format!(
+            "{} did not return a function table",
+            path.display()
+        )));
+    }
+
+    let exposure_manual_v2 = unsafe {
+        library
+            .get::<ExposureManualV2>(b"NDIlib_recv_ptz_exposure_manual_v2\0")
+            .ok()
+            .map(|f| *f)
+    };
+
+    Ok(Runtime {
+        table: unsafe { &*table },
+        exposure_manual_v2,
+        _library: library,
+    })
+}
+
+fn load_with(open: impl FnOnce() -> Result<Runtime, Error>) -> Result<&'static Runtime, Error> {
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+
+    // if another thread won the race the library is simply closed again
+    let _ = RUNTIME.set(open()?);
+    Ok(RUNTIME.get().unwrap())
+}
+
+/// Open the runtime in `NDI_RUNTIME_DIR_V4`, falling back to the system library search path.
+fn open_default() -> Result<Runtime, Error> {
+    let redist_folder = CStr::from_bytes_with_nul(NDILIB_REDIST_FOLDER).unwrap();
+
+    match std::env::var_os(redist_folder.to_str().unwrap()) {
+        Some(dir) => {
+            let path = PathBuf::from(dir).join(library_name());
+            open(&path).or_else(|e| open(Path::new(library_name())).map_err(|_| e))
+        }
+        None => open(Path::new(library_name())),
+    }
+}
+
+pub(crate) fn load() -> Result<(), Error> {
+    load_with(open_default).map(|_| ())
+}
+
+pub(crate) fn load_from(path: &Path) -> Result<(), Error> {
+    load_with(|| open(path)).map(|_| ())
+}
+
+fn runtime() -> &'static Runtime {
+    match load_with(open_default) {
+        Ok(runtime) => runtime,
+        Err(e) => panic!(
+            "{}, call ndi::load_from() with the location of the NDI runtime",
+            e
+        ),
+    }
+}
+
+macro_rules! dispatch {
+    ($($field:ident => fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                let f = unsafe { runtime().table.$field.$name }
+                    .expect(concat!(stringify!($name), " is missing from the NDI runtime"));
+                unsafe { f($($arg),*) }
+            }
+        )*
+    };
+}
+
+/// Not part of the `NDIlib_v4` table of every runtime, this fails if the runtime does not export it.
+pub unsafe fn NDIlib_recv_ptz_exposure_manual_v2(
+    p_instance: NDIlib_recv_instance_t,
+    iris: f32,
+    gain: f32,
+    shutter_speed: f32,
+) -> bool {
+    match runtime().exposure_manual_v2 {
+        Some(f) => unsafe { f(p_instance, iris, gain, shutter_speed) },
+        None => false,
+    }
+}
+
+dispatch! {
+    __bindgen_anon_1 => fn NDIlib_initialize() -> bool;
+    __bindgen_anon_2 => fn NDIlib_destroy();
+    __bindgen_anon_3 => fn NDIlib_version() -> *const c_char;
+    __bindgen_anon_4 => fn NDIlib_is_supported_CPU() -> bool;
+    __bindgen_anon_5 => fn NDIlib_find_create(
+        p_create_settings: *const NDIlib_find_create_t,
+    ) -> NDIlib_find_instance_t;
+    __bindgen_anon_6 => fn NDIlib_find_create_v2(
+        p_create_settings: *const NDIlib_find_create_t,
+    ) -> NDIlib_find_instance_t;
+    __bindgen_anon_7 => fn NDIlib_find_destroy(p_instance: NDIlib_find_instance_t);
+    __bindgen_anon_8 => fn NDIlib_find_get_sources(
+        p_instance: NDIlib_find_instance_t,
+        p_no_sources: *mut u32,
+        timeout_in_ms: u32,
+    ) -> *const NDIlib_source_t;
+    __bindgen_anon_9 => fn NDIlib_send_create(
+        p_create_settings: *const NDIlib_send_create_t,
+    ) -> NDIlib_send_instance_t;
+    __bindgen_anon_10 => fn NDIlib_send_destroy(p_instance: NDIlib_send_instance_t);
+    __bindgen_anon_11 => fn NDIlib_send_send_video(
+        p_instance: NDIlib_send_instance_t,
+        p_video_data: *const NDIlib_video_frame_t,
+    );
+    __bindgen_anon_12 => fn NDIlib_send_send_video_async(
+        p_instance: NDIlib_send_instance_t,
+        p_video_data: *const NDIlib_video_frame_t,
+    );
+    __bindgen_anon_13 => fn NDIlib_send_send_audio(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_t,
+    );
+    __bindgen_anon_14 => fn NDIlib_send_send_metadata(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_15 => fn NDIlib_send_capture(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *mut NDIlib_metadata_frame_t,
+        timeout_in_ms: u32,
+    ) -> NDIlib_frame_type_e;
+    __bindgen_anon_16 => fn NDIlib_send_free_metadata(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_17 => fn NDIlib_send_get_tally(
+        p_instance: NDIlib_send_instance_t,
+        p_tally: *mut NDIlib_tally_t,
+        timeout_in_ms: u32,
+    ) -> bool;
+    __bindgen_anon_18 => fn NDIlib_send_get_no_connections(
+        p_instance: NDIlib_send_instance_t,
+        timeout_in_ms: u32,
+    ) -> c_int;
+    __bindgen_anon_19 => fn NDIlib_send_clear_connection_metadata(
+        p_instance: NDIlib_send_instance_t,
+    );
+    __bindgen_anon_20 => fn NDIlib_send_add_connection_metadata(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_21 => fn NDIlib_send_set_failover(
+        p_instance: NDIlib_send_instance_t,
+        p_failover_source: *const NDIlib_source_t,
+    );
+    __bindgen_anon_22 => fn NDIlib_recv_create_v2(
+        p_create_settings: *const NDIlib_recv_create_t,
+    ) -> NDIlib_recv_instance_t;
+    __bindgen_anon_23 => fn NDIlib_recv_create(
+        p_create_settings: *const NDIlib_recv_create_t,
+    ) -> NDIlib_recv_instance_t;
+    __bindgen_anon_24 => fn NDIlib_recv_destroy(p_instance: NDIlib_recv_instance_t);
+    __bindgen_anon_25 => fn NDIlib_recv_capture(
+        p_instance: NDIlib_recv_instance_t,
+        p_video_data: *mut NDIlib_video_frame_t,
+        p_audio_data: *mut NDIlib_audio_frame_t,
+        p_metadata: *mut NDIlib_metadata_frame_t,
+        timeout_in_ms: u32,
+    ) -> NDIlib_frame_type_e;
+    __bindgen_anon_26 => fn NDIlib_recv_free_video(
+        p_instance: NDIlib_recv_instance_t,
+        p_video_data: *const NDIlib_video_frame_t,
+    );
+    __bindgen_anon_27 => fn NDIlib_recv_free_audio(
+        p_instance: NDIlib_recv_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_t,
+    );
+    __bindgen_anon_28 => fn NDIlib_recv_free_metadata(
+        p_instance: NDIlib_recv_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_29 => fn NDIlib_recv_send_metadata(
+        p_instance: NDIlib_recv_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    ) -> bool;
+    __bindgen_anon_30 => fn NDIlib_recv_set_tally(
+        p_instance: NDIlib_recv_instance_t,
+        p_tally: *const NDIlib_tally_t,
+    ) -> bool;
+    __bindgen_anon_31 => fn NDIlib_recv_get_performance(
+        p_instance: NDIlib_recv_instance_t,
+        p_total: *mut NDIlib_recv_performance_t,
+        p_dropped: *mut NDIlib_recv_performance_t,
+    );
+    __bindgen_anon_32 => fn NDIlib_recv_get_queue(
+        p_instance: NDIlib_recv_instance_t,
+        p_total: *mut NDIlib_recv_queue_t,
+    );
+    __bindgen_anon_33 => fn NDIlib_recv_clear_connection_metadata(
+        p_instance: NDIlib_recv_instance_t,
+    );
+    __bindgen_anon_34 => fn NDIlib_recv_add_connection_metadata(
+        p_instance: NDIlib_recv_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_35 => fn NDIlib_recv_get_no_connections(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> c_int;
+    __bindgen_anon_36 => fn NDIlib_routing_create(
+        p_create_settings: *const NDIlib_routing_create_t,
+    ) -> NDIlib_routing_instance_t;
+    __bindgen_anon_37 => fn NDIlib_routing_destroy(p_instance: NDIlib_routing_instance_t);
+    __bindgen_anon_38 => fn NDIlib_routing_change(
+        p_instance: NDIlib_routing_instance_t,
+        p_source: *const NDIlib_source_t,
+    ) -> bool;
+    __bindgen_anon_39 => fn NDIlib_routing_clear(p_instance: NDIlib_routing_instance_t) -> bool;
+    __bindgen_anon_40 => fn NDIlib_util_send_send_audio_interleaved_16s(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_interleaved_16s_t,
+    );
+    __bindgen_anon_41 => fn NDIlib_util_audio_to_interleaved_16s(
+        p_src: *const NDIlib_audio_frame_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_16s_t,
+    );
+    __bindgen_anon_42 => fn NDIlib_util_audio_from_interleaved_16s(
+        p_src: *const NDIlib_audio_frame_interleaved_16s_t,
+        p_dst: *mut NDIlib_audio_frame_t,
+    );
+    __bindgen_anon_43 => fn NDIlib_find_wait_for_sources(
+        p_instance: NDIlib_find_instance_t,
+        timeout_in_ms: u32,
+    ) -> bool;
+    __bindgen_anon_44 => fn NDIlib_find_get_current_sources(
+        p_instance: NDIlib_find_instance_t,
+        p_no_sources: *mut u32,
+    ) -> *const NDIlib_source_t;
+    __bindgen_anon_45 => fn NDIlib_util_audio_to_interleaved_32f(
+        p_src: *const NDIlib_audio_frame_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_32f_t,
+    );
+    __bindgen_anon_46 => fn NDIlib_util_audio_from_interleaved_32f(
+        p_src: *const NDIlib_audio_frame_interleaved_32f_t,
+        p_dst: *mut NDIlib_audio_frame_t,
+    );
+    __bindgen_anon_47 => fn NDIlib_util_send_send_audio_interleaved_32f(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_interleaved_32f_t,
+    );
+    __bindgen_anon_48 => fn NDIlib_recv_free_video_v2(
+        p_instance: NDIlib_recv_instance_t,
+        p_video_data: *const NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_49 => fn NDIlib_recv_free_audio_v2(
+        p_instance: NDIlib_recv_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_50 => fn NDIlib_recv_capture_v2(
+        p_instance: NDIlib_recv_instance_t,
+        p_video_data: *mut NDIlib_video_frame_v2_t,
+        p_audio_data: *mut NDIlib_audio_frame_v2_t,
+        p_metadata: *mut NDIlib_metadata_frame_t,
+        timeout_in_ms: u32,
+    ) -> NDIlib_frame_type_e;
+    __bindgen_anon_51 => fn NDIlib_send_send_video_v2(
+        p_instance: NDIlib_send_instance_t,
+        p_video_data: *const NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_52 => fn NDIlib_send_send_video_async_v2(
+        p_instance: NDIlib_send_instance_t,
+        p_video_data: *const NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_53 => fn NDIlib_send_send_audio_v2(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_54 => fn NDIlib_util_audio_to_interleaved_16s_v2(
+        p_src: *const NDIlib_audio_frame_v2_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_16s_t,
+    );
+    __bindgen_anon_55 => fn NDIlib_util_audio_from_interleaved_16s_v2(
+        p_src: *const NDIlib_audio_frame_interleaved_16s_t,
+        p_dst: *mut NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_56 => fn NDIlib_util_audio_to_interleaved_32f_v2(
+        p_src: *const NDIlib_audio_frame_v2_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_32f_t,
+    );
+    __bindgen_anon_57 => fn NDIlib_util_audio_from_interleaved_32f_v2(
+        p_src: *const NDIlib_audio_frame_interleaved_32f_t,
+        p_dst: *mut NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_58 => fn NDIlib_recv_free_string(
+        p_instance: NDIlib_recv_instance_t,
+        p_string: *const c_char,
+    );
+    __bindgen_anon_59 => fn NDIlib_recv_ptz_is_supported(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_60 => fn NDIlib_recv_recording_is_supported(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_61 => fn NDIlib_recv_get_web_control(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> *const c_char;
+    __bindgen_anon_62 => fn NDIlib_recv_ptz_zoom(
+        p_instance: NDIlib_recv_instance_t,
+        zoom_value: f32,
+    ) -> bool;
+    __bindgen_anon_63 => fn NDIlib_recv_ptz_zoom_speed(
+        p_instance: NDIlib_recv_instance_t,
+        zoom_speed: f32,
+    ) -> bool;
+    __bindgen_anon_64 => fn NDIlib_recv_ptz_pan_tilt(
+        p_instance: NDIlib_recv_instance_t,
+        pan_value: f32,
+        tilt_value: f32,
+    ) -> bool;
+    __bindgen_anon_65 => fn NDIlib_recv_ptz_pan_tilt_speed(
+        p_instance: NDIlib_recv_instance_t,
+        pan_speed: f32,
+        tilt_speed: f32,
+    ) -> bool;
+    __bindgen_anon_66 => fn NDIlib_recv_ptz_store_preset(
+        p_instance: NDIlib_recv_instance_t,
+        preset_no: c_int,
+    ) -> bool;
+    __bindgen_anon_67 => fn NDIlib_recv_ptz_recall_preset(
+        p_instance: NDIlib_recv_instance_t,
+        preset_no: c_int,
+        speed: f32,
+    ) -> bool;
+    __bindgen_anon_68 => fn NDIlib_recv_ptz_auto_focus(p_instance: NDIlib_recv_instance_t) -> bool;
+    __bindgen_anon_69 => fn NDIlib_recv_ptz_focus(
+        p_instance: NDIlib_recv_instance_t,
+        focus_value: f32,
+    ) -> bool;
+    __bindgen_anon_70 => fn NDIlib_recv_ptz_focus_speed(
+        p_instance: NDIlib_recv_instance_t,
+        focus_speed: f32,
+    ) -> bool;
+    __bindgen_anon_71 => fn NDIlib_recv_ptz_white_balance_auto(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_72 => fn NDIlib_recv_ptz_white_balance_indoor(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_73 => fn NDIlib_recv_ptz_white_balance_outdoor(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_74 => fn NDIlib_recv_ptz_white_balance_oneshot(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_75 => fn NDIlib_recv_ptz_white_balance_manual(
+        p_instance: NDIlib_recv_instance_t,
+        red: f32,
+        blue: f32,
+    ) -> bool;
+    __bindgen_anon_76 => fn NDIlib_recv_ptz_exposure_auto(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_77 => fn NDIlib_recv_ptz_exposure_manual(
+        p_instance: NDIlib_recv_instance_t,
+        exposure_level: f32,
+    ) -> bool;
+    __bindgen_anon_78 => fn NDIlib_recv_recording_start(
+        p_instance: NDIlib_recv_instance_t,
+        p_filename_hint: *const c_char,
+    ) -> bool;
+    __bindgen_anon_79 => fn NDIlib_recv_recording_stop(p_instance: NDIlib_recv_instance_t) -> bool;
+    __bindgen_anon_80 => fn NDIlib_recv_recording_set_audio_level(
+        p_instance: NDIlib_recv_instance_t,
+        level_dB: f32,
+    ) -> bool;
+    __bindgen_anon_81 => fn NDIlib_recv_recording_is_recording(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_82 => fn NDIlib_recv_recording_get_filename(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> *const c_char;
+    __bindgen_anon_83 => fn NDIlib_recv_recording_get_error(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> *const c_char;
+    __bindgen_anon_84 => fn NDIlib_recv_recording_get_times(
+        p_instance: NDIlib_recv_instance_t,
+        p_times: *mut NDIlib_recv_recording_time_t,
+    ) -> bool;
+    __bindgen_anon_85 => fn NDIlib_recv_create_v3(
+        p_create_settings: *const NDIlib_recv_create_v3_t,
+    ) -> NDIlib_recv_instance_t;
+    __bindgen_anon_86 => fn NDIlib_recv_connect(
+        p_instance: NDIlib_recv_instance_t,
+        p_src: *const NDIlib_source_t,
+    );
+    __bindgen_anon_87 => fn NDIlib_framesync_create(
+        p_receiver: NDIlib_recv_instance_t,
+    ) -> NDIlib_framesync_instance_t;
+    __bindgen_anon_88 => fn NDIlib_framesync_destroy(p_instance: NDIlib_framesync_instance_t);
+    __bindgen_anon_89 => fn NDIlib_framesync_capture_audio(
+        p_instance: NDIlib_framesync_instance_t,
+        p_audio_data: *mut NDIlib_audio_frame_v2_t,
+        sample_rate: c_int,
+        no_channels: c_int,
+        no_samples: c_int,
+    );
+    __bindgen_anon_90 => fn NDIlib_framesync_free_audio(
+        p_instance: NDIlib_framesync_instance_t,
+        p_audio_data: *mut NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_91 => fn NDIlib_framesync_capture_video(
+        p_instance: NDIlib_framesync_instance_t,
+        p_video_data: *mut NDIlib_video_frame_v2_t,
+        field_type: NDIlib_frame_format_type_e,
+    );
+    __bindgen_anon_92 => fn NDIlib_framesync_free_video(
+        p_instance: NDIlib_framesync_instance_t,
+        p_video_data: *mut NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_93 => fn NDIlib_util_send_send_audio_interleaved_32s(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_interleaved_32s_t,
+    );
+    __bindgen_anon_94 => fn NDIlib_util_audio_to_interleaved_32s_v2(
+        p_src: *const NDIlib_audio_frame_v2_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_32s_t,
+    );
+    __bindgen_anon_95 => fn NDIlib_util_audio_from_interleaved_32s_v2(
+        p_src: *const NDIlib_audio_frame_interleaved_32s_t,
+        p_dst: *mut NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_96 => fn NDIlib_send_get_source_name(
+        p_instance: NDIlib_send_instance_t,
+    ) -> *const NDIlib_source_t;
+    __bindgen_anon_97 => fn NDIlib_send_send_audio_v3(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_v3_t,
+    );
+    __bindgen_anon_98 => fn NDIlib_util_V210_to_P216(
+        p_src_v210: *const NDIlib_video_frame_v2_t,
+        p_dst_p216: *mut NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_99 => fn NDIlib_util_P216_to_V210(
+        p_src_p216: *const NDIlib_video_frame_v2_t,
+        p_dst_v210: *mut NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_100 => fn NDIlib_routing_get_no_connections(
+        p_instance: NDIlib_routing_instance_t,
+        timeout_in_ms: u32,
+    ) -> c_int;
+    __bindgen_anon_101 => fn NDIlib_routing_get_source_name(
+        p_instance: NDIlib_routing_instance_t,
+    ) -> *const NDIlib_source_t;
+    __bindgen_anon_102 => fn NDIlib_recv_capture_v3(
+        p_instance: NDIlib_recv_instance_t,
+        p_video_data: *mut NDIlib_video_frame_v2_t,
+        p_audio_data: *mut NDIlib_audio_frame_v3_t,
+        p_metadata: *mut NDIlib_metadata_frame_t,
+        timeout_in_ms: u32,
+    ) -> NDIlib_frame_type_e;
+    __bindgen_anon_103 => fn NDIlib_recv_free_audio_v3(
+        p_instance: NDIlib_recv_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_v3_t,
+    );
+    __bindgen_anon_104 => fn NDIlib_framesync_capture_audio_v2(
+        p_instance: NDIlib_framesync_instance_t,
+        p_audio_data: *mut NDIlib_audio_frame_v3_t,
+        sample_rate: c_int,
+        no_channels: c_int,
+        no_samples: c_int,
+    );
+    __bindgen_anon_105 => fn NDIlib_framesync_free_audio_v2(
+        p_instance: NDIlib_framesync_instance_t,
+        p_audio_data: *mut NDIlib_audio_frame_v3_t,
+    );
+    __bindgen_anon_106 => fn NDIlib_framesync_audio_queue_depth(
+        p_instance: NDIlib_framesync_instance_t,
+    ) -> c_int;
+}
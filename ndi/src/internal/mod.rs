@@ -4,6 +4,7 @@
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
 
+use crate::Ndi;
 use std::ops::{Deref, DerefMut};
 
 #[cfg(target_os = "windows")]
@@ -12,23 +13,41 @@ mod bindings_windows;
 #[cfg(target_os = "linux")]
 mod bindings_linux;
 
+#[cfg(feature = "dynamic")]
+pub(crate) mod dynamic;
+
 pub mod bindings {
-    #[cfg(target_os = "windows")]
+    #[cfg(all(target_os = "windows", not(feature = "dynamic")))]
     pub use super::bindings_windows::*;
 
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "dynamic")))]
     pub use super::bindings_linux::*;
+
+    #[cfg(feature = "dynamic")]
+    pub use super::dynamic::*;
 }
 
 /// Utility for adding a destructor function to a pointer which is called once the struct is dropped.
 pub(crate) struct OnDrop<P: Copy> {
     inner: P,
     destroy: fn(P),
+    // dropped after `destroy` has been called
+    _ndi: Option<Ndi>,
 }
 
 impl<T> OnDrop<*mut T> {
     pub(crate) fn new(inner: *mut T, destroy: fn(*mut T)) -> Self {
-        OnDrop { inner, destroy }
+        OnDrop {
+            inner,
+            destroy,
+            _ndi: None,
+        }
+    }
+
+    /// Keep the library initialized until the destructor has been called
+    pub(crate) fn retain(mut self, ndi: &Ndi) -> Self {
+        self._ndi = Some(ndi.clone());
+        self
     }
 }
 
@@ -15,25 +15,87 @@ use std::{
     sync::Arc,
 };
 
+/// The [`AudioFrame`] and [`OwnedAudioFrame`] structs for sending audio
+pub mod audio_frame;
+/// Builders for the capability announcements a [`Send`] makes on each connection
+pub mod capabilities;
+/// Pure-Rust conversion of video frames between the [`FourCCVideoType`] formats and RGB
+pub mod convert;
+/// The [`Deinterlacer`] for turning fielded video into progressive frames
+pub mod deinterlace;
 /// The error type used in this crate
 pub mod error;
 /// The [`Find`] struct and related constructs for finding NDI sources
 pub mod find;
+/// The [`FrameSync`] struct for time-base corrected capture from a [`Recv`]
+pub mod framesync;
 #[doc(hidden)]
 pub mod internal;
+/// The [`Ndi`] handle which keeps the NDI library initialized
+pub mod library;
+/// The [`NormalizedRecv`] for receiving video in one fixed RGB layout
+pub mod normalize;
+/// The [`Plane`] views of the pixel data of a [`VideoData`]
+pub mod plane;
+/// The [`PtzControl`] struct for controlling PTZ cameras through a [`Recv`]
+pub mod ptz;
+/// The [`RecordingControl`] struct for controlling recording through a [`Recv`]
+pub mod recording;
 /// The [`Recv`] struct and related constructs for receiving NDI
 pub mod recv;
+/// The [`Routing`] struct and related constructs for publishing virtual NDI sources
+pub mod routing;
 /// The [`Send`] struct and related constructs for sending NDI
 pub mod send;
+/// The [`SendSink`] for sending frames from async code
+#[cfg(feature = "futures")]
+pub mod sink;
+/// The [`SourceName`] struct for parsing and matching the names of sources
+pub mod source_name;
+/// The [`FrameStream`] for receiving frames in async code
+#[cfg(feature = "futures")]
+pub mod stream;
+/// The [`VideoFrame`] and [`OwnedVideoFrame`] structs for sending video
+pub mod video_frame;
 
+#[doc(hidden)]
+pub use audio_frame::*;
+#[doc(hidden)]
+pub use capabilities::*;
+#[doc(hidden)]
+pub use deinterlace::*;
 #[doc(hidden)]
 pub use error::*;
 #[doc(hidden)]
 pub use find::*;
 #[doc(hidden)]
+pub use framesync::*;
+#[doc(hidden)]
+pub use library::*;
+#[doc(hidden)]
+pub use normalize::*;
+#[doc(hidden)]
+pub use plane::*;
+#[doc(hidden)]
+pub use ptz::*;
+#[doc(hidden)]
+pub use recording::*;
+#[doc(hidden)]
 pub use recv::*;
 #[doc(hidden)]
+pub use routing::*;
+#[doc(hidden)]
 pub use send::*;
+#[cfg(feature = "futures")]
+#[doc(hidden)]
+pub use sink::*;
+#[doc(hidden)]
+pub use source_name::*;
+#[cfg(feature = "futures")]
+#[doc(hidden)]
+pub use stream::*;
+#[doc(hidden)]
+pub use video_frame::*;
 
 /// A description of the type of of frame received.
 ///
@@ -51,14 +113,16 @@ pub enum FrameType {
     /// This indicates that the settings on this input have changed.
     /// For instance, this value will be returned from [`recv::Recv::capture_all()`].
     /// when the device is known to have new settings, for instance the web URL has changed or the device
-    /// is now known to be a PTZ camera.
+    /// is now known to be a PTZ camera. Use [`Recv::device_status()`] to find out what changed.
     StatusChange = NDIlib_frame_type_e_NDIlib_frame_type_status_change as _,
     /// error occured (disconnected)
+    ///
+    /// The capture functions return [`Error::Disconnected`] instead of this.
     ErrorFrame = NDIlib_frame_type_e_NDIlib_frame_type_error as _,
 }
 
 impl TryFrom<NDIlib_frame_type_e> for FrameType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_frame_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
@@ -69,7 +133,17 @@ impl TryFrom<NDIlib_frame_type_e> for FrameType {
             NDIlib_frame_type_e_NDIlib_frame_type_status_change => Ok(FrameType::StatusChange),
             NDIlib_frame_type_e_NDIlib_frame_type_error => Ok(FrameType::ErrorFrame),
             NDIlib_frame_type_e_NDIlib_frame_type_metadata => Ok(FrameType::Metadata),
-            x => Err(InvalidEnum(x as _, "FrameType")),
+            x => Err(Error::InvalidEnum(x as _, "FrameType")),
+        }
+    }
+}
+
+impl FrameType {
+    /// Convert the return value of a capture, the connection being lost is reported as [`Error::Disconnected`]
+    pub(crate) fn from_capture(value: NDIlib_frame_type_e) -> Result<Self, Error> {
+        match FrameType::try_from(value)? {
+            FrameType::ErrorFrame => Err(Error::Disconnected),
+            frame_type => Ok(frame_type),
         }
     }
 }
@@ -109,7 +183,7 @@ pub enum FrameFormatType {
 }
 
 impl TryFrom<NDIlib_frame_format_type_e> for FrameFormatType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_frame_format_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
@@ -128,7 +202,7 @@ impl TryFrom<NDIlib_frame_format_type_e> for FrameFormatType {
             NDIlib_frame_format_type_e_NDIlib_frame_format_type_field_1 => {
                 Ok(FrameFormatType::Field1)
             }
-            x => Err(InvalidEnum(x as _, "FrameFormatType")),
+            x => Err(Error::InvalidEnum(x as _, "FrameFormatType")),
         }
     }
 }
@@ -245,7 +319,7 @@ pub enum FourCCVideoType {
 }
 
 impl TryFrom<NDIlib_FourCC_video_type_e> for FourCCVideoType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_FourCC_video_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
@@ -261,7 +335,7 @@ impl TryFrom<NDIlib_FourCC_video_type_e> for FourCCVideoType {
             NDIlib_FourCC_video_type_e_NDIlib_FourCC_type_RGBA => Ok(FourCCVideoType::RGBA),
             NDIlib_FourCC_video_type_e_NDIlib_FourCC_type_BGRX => Ok(FourCCVideoType::BGRX),
             NDIlib_FourCC_video_type_e_NDIlib_FourCC_type_RGBX => Ok(FourCCVideoType::RGBX),
-            x => Err(InvalidEnum(x as _, "FourCCVideoType")),
+            x => Err(Error::InvalidEnum(x as _, "FourCCVideoType")),
         }
     }
 }
@@ -274,61 +348,90 @@ pub enum FourCCAudioType {
 }
 
 impl TryFrom<NDIlib_FourCC_audio_type_e> for FourCCAudioType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_FourCC_audio_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
         match value {
             NDIlib_FourCC_audio_type_e_NDIlib_FourCC_type_FLTP => Ok(FourCCAudioType::FLTP),
-            x => Err(InvalidEnum(x as _, "FourCCAudioType")),
+            x => Err(Error::InvalidEnum(x as _, "FourCCAudioType")),
         }
     }
 }
 
-#[derive(Clone)]
-enum SourceParent {
-    Find(Arc<OnDrop<NDIlib_find_instance_t>>),
-    Send(Arc<OnDrop<NDIlib_send_instance_t>>),
-    None,
-}
-
 /// A descriptor of a NDI source available on the network.
-#[derive(Clone)]
+///
+/// The source owns copies of its name and address, so it stays valid after the list it came from
+/// is updated, can be sent to other threads and compared with other sources.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
 pub struct Source {
-    p_instance: NDIlib_source_t,
-    parent: SourceParent,
+    name: Option<CString>,
+    url_address: Option<CString>,
 }
 
 impl Debug for Source {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ndi::Source")
             .field("name", &self.get_name())
+            .field("url_address", &self.url_address())
             .finish()
     }
 }
 
 impl Source {
-    fn from_binding(parent: SourceParent, source: NDIlib_source_t) -> Self {
+    /// Copy a source returned by the SDK
+    pub(crate) fn from_binding(source: &NDIlib_source_t) -> Self {
+        let copy = |p_str: *const std::os::raw::c_char| {
+            if p_str.is_null() {
+                None
+            } else {
+                Some(unsafe { CStr::from_ptr(p_str) }.to_owned())
+            }
+        };
+
         Self {
-            parent,
-            p_instance: source,
+            name: copy(source.p_ndi_name),
+            url_address: copy(unsafe { source.__bindgen_anon_1.p_url_address }),
         }
     }
 
-    fn new() -> Self {
-        // From the default c++ constructor in Processing.NDI.structs.h
-        let p_instance = NDIlib_source_t {
-            p_ndi_name: null(),
+    /// The struct the SDK expects, which points into `self`
+    pub(crate) fn to_binding(&self) -> NDIlib_source_t {
+        let as_ptr = |s: &Option<CString>| s.as_ref().map_or(null(), |s| s.as_ptr());
+
+        NDIlib_source_t {
+            p_ndi_name: as_ptr(&self.name),
             __bindgen_anon_1: NDIlib_source_t__bindgen_ty_1 {
-                p_ip_address: null(),
+                p_url_address: as_ptr(&self.url_address),
             },
-        };
-        Self {
-            parent: SourceParent::None,
-            p_instance,
         }
     }
 
+    /// A source to connect to by its full name, in the form `MACHINE_NAME (NDI_SOURCE_NAME)`
+    ///
+    /// The SDK looks the source up on the network when connecting,
+    /// so it does not need to have been found with [`Find`] first.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        let name = CString::new(name).map_err(|_| Error::InvalidString("name"))?;
+        Ok(Self {
+            name: Some(name),
+            url_address: None,
+        })
+    }
+
+    /// A source to connect to directly by its address, without looking it up on the network
+    ///
+    /// The address is usually a [`std::net::SocketAddr`] or a string in the form `"ip:port"`,
+    /// such as the one returned by [`Source::url_address()`].
+    pub fn from_address<A: Display>(address: A) -> Result<Self, Error> {
+        let address =
+            CString::new(address.to_string()).map_err(|_| Error::InvalidString("address"))?;
+        Ok(Self {
+            name: None,
+            url_address: Some(address),
+        })
+    }
+
     /// A UTF8 string that provides a user readable name for this source.
     ///
     /// This can be used for serialization, etc... and comprises the machine
@@ -337,22 +440,21 @@ impl Source {
     /// If the parameter was passed either as NULL, or an EMPTY string then
     /// the specific IP address and port number from below is used.
     pub fn get_name(&self) -> String {
-        let name_char_ptr: *mut std::os::raw::c_char = self.p_instance.p_ndi_name as _;
-        if name_char_ptr.is_null() {
-            return String::new();
-        }
-        let name = unsafe {
-            CStr::from_ptr(name_char_ptr)
-                .to_owned()
-                .to_string_lossy()
-                .to_string()
-        };
-        name
+        self.name
+            .as_ref()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
     }
-}
 
-unsafe impl core::marker::Send for Source {}
-unsafe impl core::marker::Sync for Source {}
+    /// The URL or IP address and port of this source, if the SDK provided it
+    ///
+    /// Connecting by address skips looking up the source by name on the network.
+    pub fn url_address(&self) -> Option<String> {
+        self.url_address
+            .as_ref()
+            .map(|address| address.to_string_lossy().into_owned())
+    }
+}
 
 /// Tally information
 #[repr(C)]
@@ -400,6 +502,7 @@ impl Into<NDIlib_tally_t> for Tally {
 
 enum VideoParent {
     Recv(Arc<OnDrop<NDIlib_recv_instance_t>>),
+    FrameSync(Arc<FrameSyncInstance>),
     Owned,
 }
 
@@ -448,6 +551,16 @@ impl VideoData {
         }
     }
 
+    fn from_binding_framesync(
+        framesync: Arc<FrameSyncInstance>,
+        p_instance: NDIlib_video_frame_v2_t,
+    ) -> Self {
+        Self {
+            p_instance,
+            parent: VideoParent::FrameSync(framesync),
+        }
+    }
+
     /// Create an empty video frame
     pub fn new() -> Self {
         Self {
@@ -471,46 +584,6 @@ impl VideoData {
         }
     }
 
-    /// Create VideoData from a raw memory buffer, with custom settings
-    pub fn from_buffer(
-        width: i32,
-        height: i32,
-        fourcc: FourCCVideoType,
-        framerate_numerator: i32,
-        framerate_denominator: i32,
-        frame_format: FrameFormatType,
-        timecode: i64,
-        stride: i32,
-        metadata: Option<&CStr>,
-        buffer: &mut [u8],
-    ) -> Self {
-        Self {
-            p_instance: NDIlib_video_frame_v2_t {
-                xres: width,
-                yres: height,
-                FourCC: fourcc as _,
-                frame_rate_N: framerate_numerator,
-                frame_rate_D: framerate_denominator,
-                picture_aspect_ratio: width as f32 / height as f32,
-                frame_format_type: frame_format as _,
-                timecode,
-                p_data: buffer.as_mut_ptr(),
-                // can't seem to figure out which FourCC types correspond to the "compressed format"
-                // since even for UYUV and such, it seems to still use line_stride_in_bytes rather than data_size_in_bytes
-                __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
-                    line_stride_in_bytes: stride,
-                },
-                p_metadata: if let Some(metadata) = metadata {
-                    metadata.as_ptr()
-                } else {
-                    null()
-                },
-                timestamp: NDIlib_recv_timestamp_undefined,
-            },
-            parent: VideoParent::Owned,
-        }
-    }
-
     /// The width of the frame expressed in pixels.
     ///
     /// Note that, because data is internally all considered
@@ -528,8 +601,8 @@ impl VideoData {
     /// The FourCC pixel format for this buffer.
     ///
     /// See [`FourCCVideoType`] for details on possible values
-    pub fn four_cc(&self) -> FourCCVideoType {
-        FourCCVideoType::try_from(self.p_instance.FourCC).unwrap()
+    pub fn four_cc(&self) -> Result<FourCCVideoType, Error> {
+        FourCCVideoType::try_from(self.p_instance.FourCC)
     }
 
     /// The numerator of the framerate of the current frame.
@@ -573,8 +646,8 @@ impl VideoData {
     }
 
     /// The frame format type of a video
-    pub fn frame_format_type(&self) -> FrameFormatType {
-        FrameFormatType::try_from(self.p_instance.frame_format_type).unwrap()
+    pub fn frame_format_type(&self) -> Result<FrameFormatType, Error> {
+        FrameFormatType::try_from(self.p_instance.frame_format_type)
     }
 
     /// The timecode of this frame in 100 ns intervals.
@@ -594,20 +667,72 @@ impl VideoData {
         self.p_instance.p_data
     }
 
+    /// The video data of all planes of the frame
+    ///
+    /// For uncompressed formats the size is computed from the [`FourCCVideoType`],
+    /// for compressed formats it is [`data_size_in_bytes()`](Self::data_size_in_bytes).
+    /// Use [`planes()`](Self::planes) to access the individual planes.
+    pub fn data(&self) -> &[u8] {
+        let size = match self.four_cc() {
+            Ok(four_cc) => four_cc.buffer_size(
+                self.line_stride_in_bytes().unwrap_or(0) as _,
+                self.height() as _,
+            ),
+            Err(_) => self.data_size_in_bytes().unwrap_or(0) as _,
+        };
+
+        if self.p_instance.p_data.is_null() || size == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.p_instance.p_data, size) }
+    }
+
+    /// The planes of the frame, in the order they are laid out in memory
+    ///
+    /// Packed formats such as [`FourCCVideoType::BGRA`] have a single [`PlaneKind::Packed`] plane.
+    /// Returns [`Error::InvalidEnum`] for compressed formats, which have no planes.
+    pub fn planes(&self) -> Result<Vec<Plane<'_>>, Error> {
+        let four_cc = self.four_cc()?;
+        let data = self.data();
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let layouts = four_cc.plane_layouts(
+            self.width() as _,
+            self.height() as _,
+            self.line_stride_in_bytes().unwrap_or(0) as _,
+        );
+        Ok(layouts
+            .iter()
+            .map(|layout| Plane::new(layout, data))
+            .collect())
+    }
+
     /// This is the inter-line stride of the video data, in bytes.
+    ///
+    /// A stride of 0 from the sender is reported as the stride of packed lines.
+    /// Returns `None` for compressed formats, which have no stride.
     pub fn line_stride_in_bytes(&self) -> Option<u32> {
-        // TODO: detect whether it is a compressed type
-
-        // If the FourCC is not a compressed type, then this will be the
-        // inter-line stride of the video data in bytes. If the stride is 0,
-        // then it will default to sizeof(one pixel)*xres.
-        unsafe { Some(self.p_instance.__bindgen_anon_1.line_stride_in_bytes as _) }
+        // the union holds the stride exactly when the FourCC is an uncompressed type
+        let four_cc = self.four_cc().ok()?;
+        let stride = unsafe { self.p_instance.__bindgen_anon_1.line_stride_in_bytes };
+        if stride > 0 {
+            Some(stride as _)
+        } else {
+            Some(four_cc.packed_line_stride(self.width() as _) as _)
+        }
     }
 
     /// The size of the p_data buffer in bytes.
+    ///
+    /// This is only set for compressed formats, which [`four_cc()`](Self::four_cc) does not know,
+    /// use [`data()`](Self::data) for the size of uncompressed frames.
     pub fn data_size_in_bytes(&self) -> Option<u32> {
-        // If the FourCC is a compressed type, then this will be the size of the
-        // p_data buffer in bytes.
+        // the union holds the size exactly when the FourCC is a compressed type
+        if self.four_cc().is_ok() {
+            return None;
+        }
         unsafe { Some(self.p_instance.__bindgen_anon_1.data_size_in_bytes as _) }
     }
 
@@ -649,6 +774,9 @@ impl Drop for VideoData {
             VideoParent::Recv(recv) => unsafe {
                 NDIlib_recv_free_video_v2(***recv, &mut self.p_instance);
             },
+            VideoParent::FrameSync(framesync) => unsafe {
+                NDIlib_framesync_free_video(*framesync.p_instance, &mut self.p_instance);
+            },
             VideoParent::Owned => {}
         }
     }
@@ -656,6 +784,7 @@ impl Drop for VideoData {
 
 enum AudioParent {
     Recv(Arc<OnDrop<NDIlib_recv_instance_t>>),
+    FrameSync(Arc<FrameSyncInstance>),
     Owned,
 }
 
@@ -693,7 +822,19 @@ impl AudioData {
         }
     }
 
-    /// Create new instance of AudioData
+    fn from_binding_framesync(
+        framesync: Arc<FrameSyncInstance>,
+        p_instance: NDIlib_audio_frame_v3_t,
+    ) -> Self {
+        Self {
+            p_instance,
+            parent: AudioParent::FrameSync(framesync),
+        }
+    }
+
+    /// Create new empty instance of AudioData
+    ///
+    /// Use [`AudioFrame`] or [`OwnedAudioFrame`] to create audio frames for sending.
     pub fn new() -> Self {
         Self {
             p_instance: NDIlib_audio_frame_v3_t {
@@ -706,7 +847,7 @@ impl AudioData {
                 __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
                     channel_stride_in_bytes: 0,
                 },
-                p_metadata: "".as_ptr() as _,
+                p_metadata: null(),
                 timestamp: 0,
             },
             parent: AudioParent::Owned,
@@ -762,12 +903,8 @@ impl AudioData {
     /// What FourCC type is for this frame
     ///
     /// There is currently one supported format: FLTP.
-    pub fn four_cc(&self) -> FourCCAudioType {
-        #[allow(non_upper_case_globals)]
-        match self.p_instance.FourCC {
-            NDIlib_FourCC_audio_type_e_NDIlib_FourCC_type_FLTP => FourCCAudioType::FLTP,
-            x => panic!("Unknown NDI FourCC Audio type encountered: {}", x),
-        }
+    pub fn four_cc(&self) -> Result<FourCCAudioType, Error> {
+        FourCCAudioType::try_from(self.p_instance.FourCC)
     }
 
     /// The stride in bytes for a single channel.
@@ -775,11 +912,11 @@ impl AudioData {
     /// This is the number of bytes that are used to step from one audio
     /// channel to another.
     ///
-    pub fn channel_stride_in_bytes(&self) -> u32 {
-        match self.four_cc() {
-            FourCCAudioType::FLTP => unsafe {
-                self.p_instance.__bindgen_anon_1.channel_stride_in_bytes as _
-            },
+    pub fn channel_stride_in_bytes(&self) -> Result<u32, Error> {
+        match self.four_cc()? {
+            FourCCAudioType::FLTP => {
+                Ok(unsafe { self.p_instance.__bindgen_anon_1.channel_stride_in_bytes as _ })
+            }
         }
     }
 
@@ -797,6 +934,80 @@ impl AudioData {
             .to_string();
         metadata
     }
+
+    fn to_binding_v2(&self) -> Result<NDIlib_audio_frame_v2_t, Error> {
+        Ok(NDIlib_audio_frame_v2_t {
+            sample_rate: self.p_instance.sample_rate,
+            no_channels: self.p_instance.no_channels,
+            no_samples: self.p_instance.no_samples,
+            timecode: self.p_instance.timecode,
+            p_data: self.p_instance.p_data as _,
+            channel_stride_in_bytes: self.channel_stride_in_bytes()? as _,
+            p_metadata: self.p_instance.p_metadata,
+            timestamp: self.p_instance.timestamp,
+        })
+    }
+
+    fn interleaved_len(&self) -> usize {
+        self.no_samples() as usize * self.no_channels() as usize
+    }
+
+    /// Convert to interleaved 16bpp samples
+    ///
+    /// `reference_level` specifies how many dB above the reference level (+4dBU) the full range of 16 bit audio is.
+    /// The floating point -1.0 to +1.0 range of NDI audio is defined as a professional audio reference level of +4dBU.
+    /// When receiving audio, +20dB is a good default. This means that the full 16 bit range corresponds to professional
+    /// level audio with 20dB of headroom, it might sound soft because you have 20dB of headroom before clipping.
+    /// 0dB will likely result in clipping since it is not uncommon for audio to exceed the reference level.
+    pub fn to_interleaved_16s(&self, reference_level: i32) -> Result<Vec<i16>, Error> {
+        let src = self.to_binding_v2()?;
+        let mut data = vec![0; self.interleaved_len()];
+        let mut dst = NDIlib_audio_frame_interleaved_16s_t {
+            sample_rate: self.p_instance.sample_rate,
+            no_channels: self.p_instance.no_channels,
+            no_samples: self.p_instance.no_samples,
+            timecode: self.p_instance.timecode,
+            reference_level,
+            p_data: data.as_mut_ptr(),
+        };
+        unsafe { NDIlib_util_audio_to_interleaved_16s_v2(&src, &mut dst) };
+        Ok(data)
+    }
+
+    /// Convert to interleaved 32bpp samples
+    ///
+    /// See [`AudioData::to_interleaved_16s()`] for the meaning of `reference_level`.
+    pub fn to_interleaved_32s(&self, reference_level: i32) -> Result<Vec<i32>, Error> {
+        let src = self.to_binding_v2()?;
+        let mut data = vec![0; self.interleaved_len()];
+        let mut dst = NDIlib_audio_frame_interleaved_32s_t {
+            sample_rate: self.p_instance.sample_rate,
+            no_channels: self.p_instance.no_channels,
+            no_samples: self.p_instance.no_samples,
+            timecode: self.p_instance.timecode,
+            reference_level,
+            p_data: data.as_mut_ptr(),
+        };
+        unsafe { NDIlib_util_audio_to_interleaved_32s_v2(&src, &mut dst) };
+        Ok(data)
+    }
+
+    /// Convert to interleaved floating point samples
+    ///
+    /// The -1.0 to +1.0 range corresponds to the professional audio reference level of +4dBU.
+    pub fn to_interleaved_32f(&self) -> Result<Vec<f32>, Error> {
+        let src = self.to_binding_v2()?;
+        let mut data = vec![0.0; self.interleaved_len()];
+        let mut dst = NDIlib_audio_frame_interleaved_32f_t {
+            sample_rate: self.p_instance.sample_rate,
+            no_channels: self.p_instance.no_channels,
+            no_samples: self.p_instance.no_samples,
+            timecode: self.p_instance.timecode,
+            p_data: data.as_mut_ptr(),
+        };
+        unsafe { NDIlib_util_audio_to_interleaved_32f_v2(&src, &mut dst) };
+        Ok(data)
+    }
 }
 
 impl Drop for AudioData {
@@ -805,6 +1016,9 @@ impl Drop for AudioData {
             AudioParent::Recv(recv) => unsafe {
                 NDIlib_recv_free_audio_v3(***recv, &self.p_instance);
             },
+            AudioParent::FrameSync(framesync) => unsafe {
+                NDIlib_framesync_free_audio_v2(*framesync.p_instance, &mut self.p_instance);
+            },
             AudioParent::Owned => {}
         }
     }
@@ -857,17 +1071,19 @@ impl MetaData {
     }
 
     /// Create new metadata struct
-    pub fn new(length: u32, timecode: i64, data: String) -> Self {
-        let p_data = CString::new(data).unwrap().into_raw();
+    pub fn new(length: u32, timecode: i64, data: String) -> Result<Self, Error> {
+        let p_data = CString::new(data)
+            .map_err(|_| Error::InvalidString("data"))?
+            .into_raw();
         let p_instance = NDIlib_metadata_frame_t {
             length: length as _,
             timecode,
             p_data,
         };
-        Self {
+        Ok(Self {
             p_instance,
             parent: MetaDataParent::Owned,
-        }
+        })
     }
 
     /// The length of the string in UTF8 characters. This includes the NULL terminating character.
@@ -900,40 +1116,67 @@ impl Drop for MetaData {
             MetaDataParent::Send(send) => unsafe {
                 NDIlib_send_free_metadata(***send, &mut self.p_instance);
             },
-            MetaDataParent::Owned => {}
+            // the string was created by `MetaData::new()`
+            MetaDataParent::Owned => unsafe {
+                drop(CString::from_raw(self.p_instance.p_data));
+            },
         }
     }
 }
 
-/// Start the library
+/// Load the NDI runtime library
 ///
-/// This is not actually required, but will start the libraries which might get
-/// you slightly better performance in some cases. In general it is more "correct" to
-/// call this although it is not required. There is no way to call this that would have
-/// an adverse impact on anything.
-/// This will return Err if the CPU is not sufficiently capable to run NDILib
-/// currently NDILib requires SSE4.2 instructions (see documentation). You can verify
-/// a specific CPU against the library with a call to [`is_supported_CPU()`]
-pub fn initialize() -> Result<(), NotSupported> {
-    if !unsafe { NDIlib_initialize() } {
-        return Err(NotSupported);
+/// Only available with the `dynamic` feature, where the library is loaded at runtime instead of being linked.
+/// The library is looked up in the `NDI_RUNTIME_DIR_V4` directory set by the NDI runtime installer
+/// and then on the system library search path.
+/// Nothing happens if a runtime has already been loaded.
+///
+/// [`Ndi::new()`] calls this if no runtime has been loaded yet, so this only needs to be called
+/// to find out whether the runtime is installed before initializing the library.
+#[cfg(feature = "dynamic")]
+pub fn load() -> Result<(), Error> {
+    internal::dynamic::load()
+}
+
+/// Load the NDI runtime library from `path`
+///
+/// Only available with the `dynamic` feature, use this if the runtime is shipped alongside your application.
+/// Nothing happens if a runtime has already been loaded.
+#[cfg(feature = "dynamic")]
+pub fn load_from<P: AsRef<std::path::Path>>(path: P) -> Result<(), Error> {
+    internal::dynamic::load_from(path.as_ref())
+}
+
+#[test]
+fn source_owns_strings() {
+    let name = CString::new("MACHINE (Stream)").unwrap();
+    let address = CString::new("192.168.0.2:5961").unwrap();
+    let binding = NDIlib_source_t {
+        p_ndi_name: name.as_ptr(),
+        __bindgen_anon_1: NDIlib_source_t__bindgen_ty_1 {
+            p_url_address: address.as_ptr(),
+        },
     };
 
-    Ok(())
+    let source = Source::from_binding(&binding);
+    drop((name, address));
+    assert_eq!(source.get_name(), "MACHINE (Stream)");
+    assert_eq!(source.url_address().as_deref(), Some("192.168.0.2:5961"));
+    assert_eq!(Source::from_binding(&source.to_binding()), source);
+    assert_eq!(Source::default().get_name(), "");
 }
 
-/// Destroy everything associated with the library
-///
-/// This is not actually required, but will end the libraries which might get
-/// you slightly better performance in some cases.In general it is more "correct" to
-/// call this although it is not required.
-/// This will destroy everything associated with the library so use it with due caution.
-pub unsafe fn cleanup() {
-    unsafe { NDIlib_destroy() };
-}
-
-/// Recover whether the current CPU in the system is capable of running NDILib.
-#[allow(non_snake_case)]
-pub fn is_supported_CPU() -> bool {
-    unsafe { NDIlib_is_supported_CPU() }
+#[test]
+fn source_constructors() {
+    let source = Source::from_name("MACHINE (Stream)").unwrap();
+    assert_eq!(source.get_name(), "MACHINE (Stream)");
+    assert_eq!(source.url_address(), None);
+
+    let address: std::net::SocketAddr = "192.168.0.2:5961".parse().unwrap();
+    let source = Source::from_address(address).unwrap();
+    assert_eq!(source, Source::from_address("192.168.0.2:5961").unwrap());
+    assert_eq!(source.url_address().as_deref(), Some("192.168.0.2:5961"));
+    assert!(source.to_binding().p_ndi_name.is_null());
+
+    assert!(Source::from_name("a\0b").is_err());
 }
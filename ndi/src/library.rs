@@ -0,0 +1,101 @@
+use super::*;
+use std::sync::Mutex;
+
+// The number of live `Ndi` handles, the library is destroyed once this drops back to zero.
+static REFERENCES: Mutex<usize> = Mutex::new(0);
+
+/// A handle to the initialized NDI library
+///
+/// The library is initialized when the first handle is created and destroyed
+/// once the last handle is dropped. Every [`Find`], [`Recv`], [`Send`] and [`Routing`]
+/// (and the frames received from them) keeps a handle, so the library
+/// can never be destroyed while any of them are still alive.
+/// A [`Source`] only holds copies of its name and address, so it can outlive the library.
+///
+/// Handles are cheap to clone.
+pub struct Ndi {
+    _private: (),
+}
+
+impl Ndi {
+    /// Initialize the library, or get another handle to it if it is already initialized
+    ///
+    /// With the `dynamic` feature this loads the runtime first if `ndi::load()` has not been called,
+    /// returning [`Error::Load`] if it is not installed.
+    /// This will return Err if the CPU is not sufficiently capable to run NDILib
+    /// currently NDILib requires SSE4.2 instructions (see documentation). You can verify
+    /// a specific CPU against the library with a call to [`Ndi::is_supported_CPU()`]
+    pub fn new() -> Result<Self, Error> {
+        #[cfg(feature = "dynamic")]
+        internal::dynamic::load()?;
+
+        let mut references = REFERENCES.lock().unwrap();
+        if *references == 0 && !unsafe { NDIlib_initialize() } {
+            return Err(Error::NotSupported("NDI on this CPU"));
+        }
+
+        *references += 1;
+        Ok(Self { _private: () })
+    }
+
+    /// The version string of the NDI library, for instance `"NDI SDK LINUX 13:20:27 Jun 25 2021 4.6.2"`
+    pub fn version(&self) -> String {
+        let p_version = unsafe { NDIlib_version() };
+        if p_version.is_null() {
+            return String::new();
+        }
+
+        unsafe { CStr::from_ptr(p_version) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Recover whether the current CPU in the system is capable of running NDILib.
+    ///
+    /// This does not require the library to be initialized.
+    /// With the `dynamic` feature this is false if the runtime cannot be loaded.
+    #[allow(non_snake_case)]
+    pub fn is_supported_CPU() -> bool {
+        #[cfg(feature = "dynamic")]
+        if internal::dynamic::load().is_err() {
+            return false;
+        }
+
+        unsafe { NDIlib_is_supported_CPU() }
+    }
+}
+
+impl Clone for Ndi {
+    fn clone(&self) -> Self {
+        *REFERENCES.lock().unwrap() += 1;
+        Self { _private: () }
+    }
+}
+
+impl Drop for Ndi {
+    fn drop(&mut self) {
+        let mut references = REFERENCES.lock().unwrap();
+        *references -= 1;
+        if *references == 0 {
+            unsafe { NDIlib_destroy() };
+        }
+    }
+}
+
+impl Debug for Ndi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ndi").finish()
+    }
+}
+
+/// Start the library
+///
+/// This is the same as [`Ndi::new()`]. The returned handle has to be passed to
+/// the builders of [`Find`], [`Recv`], [`Send`] and [`Routing`], and the library
+/// stays initialized until the last of them and the handle itself are dropped.
+/// This will return Err if the CPU is not sufficiently capable to run NDILib
+/// currently NDILib requires SSE4.2 instructions (see documentation). You can verify
+/// a specific CPU against the library with a call to [`Ndi::is_supported_CPU()`]
+pub fn initialize() -> Result<Ndi, Error> {
+    Ndi::new()
+}
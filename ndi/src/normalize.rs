@@ -0,0 +1,239 @@
+use super::*;
+use crate::{
+    convert::{to_rgb, ColorSpace, RgbFormat},
+    recv::{capture_frame, RecvInstance},
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+enum NormalizedData {
+    Received(VideoData),
+    Converted(Vec<u8>),
+}
+
+/// A received video frame in the [`RgbFormat`] requested from a [`NormalizedRecv`], with packed lines
+pub struct NormalizedVideoFrame {
+    p_instance: NDIlib_video_frame_v2_t,
+    data: NormalizedData,
+    format: RgbFormat,
+    converted_from: Option<FourCCVideoType>,
+    metadata: String,
+}
+
+unsafe impl core::marker::Send for NormalizedVideoFrame {}
+unsafe impl core::marker::Sync for NormalizedVideoFrame {}
+
+impl NormalizedVideoFrame {
+    fn new(
+        video: VideoData,
+        format: RgbFormat,
+        color_space: Option<ColorSpace>,
+    ) -> Result<Self, Error> {
+        let four_cc = video.four_cc()?;
+        let packed = video.line_stride_in_bytes()
+            == Some(four_cc.packed_line_stride(video.width() as _) as _);
+        let matches = match format {
+            RgbFormat::RGBA8 => four_cc == FourCCVideoType::RGBA,
+            RgbFormat::BGRA8 => four_cc == FourCCVideoType::BGRA,
+            RgbFormat::RGBA16 => false,
+        };
+
+        // the pixel data is not valid beyond the lifetime of `video`
+        let mut p_instance = video.p_instance;
+        p_instance.p_data = null_mut();
+        p_instance.p_metadata = null();
+        let metadata = video.metadata();
+
+        let (data, converted_from) = if matches && packed {
+            (NormalizedData::Received(video), None)
+        } else {
+            let color_space = color_space
+                .unwrap_or_else(|| ColorSpace::for_resolution(video.width(), video.height()));
+            let data = to_rgb(&video, format, color_space)?;
+            (NormalizedData::Converted(data), Some(four_cc))
+        };
+
+        Ok(Self {
+            p_instance,
+            data,
+            format,
+            converted_from,
+            metadata,
+        })
+    }
+
+    /// The pixel data, `width() * height()` pixels in the layout of `format()`
+    pub fn data(&self) -> &[u8] {
+        match &self.data {
+            NormalizedData::Received(video) => video.data(),
+            NormalizedData::Converted(data) => data,
+        }
+    }
+
+    /// Take the pixel data, which copies it if it was not converted
+    pub fn into_data(self) -> Vec<u8> {
+        match self.data {
+            NormalizedData::Received(video) => video.data().to_vec(),
+            NormalizedData::Converted(data) => data,
+        }
+    }
+
+    /// The layout of the pixel data
+    pub fn format(&self) -> RgbFormat {
+        self.format
+    }
+
+    /// The FourCC the SDK delivered the frame in, if it had to be converted
+    ///
+    /// This is `None` if the frame was received in the requested layout and was passed through.
+    pub fn converted_from(&self) -> Option<FourCCVideoType> {
+        self.converted_from
+    }
+
+    /// The width of the frame expressed in pixels.
+    pub fn width(&self) -> u32 {
+        self.p_instance.xres as _
+    }
+
+    /// The height of the frame expressed in pixels.
+    pub fn height(&self) -> u32 {
+        self.p_instance.yres as _
+    }
+
+    /// The numerator of the framerate of the current frame.
+    pub fn frame_rate_n(&self) -> u32 {
+        self.p_instance.frame_rate_N as _
+    }
+
+    /// The denominator of the framerate of the current frame.
+    pub fn frame_rate_d(&self) -> u32 {
+        self.p_instance.frame_rate_D as _
+    }
+
+    /// The SDK defines picture aspect ratio (as opposed to pixel aspect ratios).
+    pub fn picture_aspect_ratio(&self) -> f32 {
+        self.p_instance.picture_aspect_ratio
+    }
+
+    /// The frame format type of a video
+    pub fn frame_format_type(&self) -> Result<FrameFormatType, Error> {
+        FrameFormatType::try_from(self.p_instance.frame_format_type)
+    }
+
+    /// The timecode of this frame in 100 ns intervals.
+    pub fn timecode(&self) -> i64 {
+        self.p_instance.timecode
+    }
+
+    /// A per-frame timestamp filled in by the NDI SDK, see [`VideoData::timestamp()`]
+    pub fn timestamp(&self) -> Option<i64> {
+        let timestamp = self.p_instance.timestamp;
+        if timestamp == NDIlib_recv_timestamp_undefined {
+            None
+        } else {
+            Some(timestamp)
+        }
+    }
+
+    /// A per frame metadata stream that should be XML
+    pub fn metadata(&self) -> &str {
+        &self.metadata
+    }
+}
+
+/// Receives the video of a [`Recv`] in one fixed [`RgbFormat`], with packed lines
+///
+/// A worker thread captures the video frames and converts them
+/// whenever the SDK delivers them in a different FourCC or line stride,
+/// which it can do regardless of the [`RecvColorFormat`] requested.
+/// Video should not also be captured from the [`Recv`] itself while this exists,
+/// as every frame only goes to one of them.
+pub struct NormalizedRecv {
+    frames: Option<Receiver<Result<NormalizedVideoFrame, Error>>>,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl NormalizedRecv {
+    /// Start receiving the video of `recv` in `format`
+    ///
+    /// YUV frames are converted with the color space NDI uses for their resolution,
+    /// see [`ColorSpace::for_resolution()`].
+    pub fn new(recv: &Recv, format: RgbFormat) -> Self {
+        Self::start(recv, format, None)
+    }
+
+    /// Start receiving the video of `recv` in `format`, converting YUV frames with `color_space`
+    pub fn with_color_space(recv: &Recv, format: RgbFormat, color_space: ColorSpace) -> Self {
+        Self::start(recv, format, Some(color_space))
+    }
+
+    fn start(recv: &Recv, format: RgbFormat, color_space: Option<ColorSpace>) -> Self {
+        // a small queue, so that frames back up in the SDK when the caller falls behind
+        let (sender, frames) = sync_channel(2);
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let p_instance = RecvInstance(recv.p_instance.clone());
+        let worker_stop = stop.clone();
+        let worker = thread::spawn(move || {
+            normalize_video(p_instance, format, color_space, sender, worker_stop)
+        });
+
+        Self {
+            frames: Some(frames),
+            stop,
+            worker: Some(worker),
+        }
+    }
+
+    /// Receive the next video frame
+    ///
+    /// Returns [`Error::Timeout`] if no frame is received within `timeout_ms`,
+    /// and [`Error::Disconnected`] if the connection is lost.
+    pub fn capture_video(&self, timeout_ms: u32) -> Result<NormalizedVideoFrame, Error> {
+        let frames = self.frames.as_ref().ok_or(Error::Disconnected)?;
+        match frames.recv_timeout(Duration::from_millis(timeout_ms as _)) {
+            Ok(frame) => frame,
+            Err(RecvTimeoutError::Timeout) => Err(Error::Timeout),
+            Err(RecvTimeoutError::Disconnected) => Err(Error::Disconnected),
+        }
+    }
+}
+
+impl Drop for NormalizedRecv {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // unblocks a worker waiting for room in the queue
+        self.frames = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn normalize_video(
+    RecvInstance(p_instance): RecvInstance,
+    format: RgbFormat,
+    color_space: Option<ColorSpace>,
+    frames: SyncSender<Result<NormalizedVideoFrame, Error>>,
+    stop: Arc<AtomicBool>,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        // a short timeout, to notice when to stop
+        let frame = match capture_frame(&p_instance, FrameFilter::VIDEO, 100) {
+            Ok(Frame::Video(video)) => NormalizedVideoFrame::new(video, format, color_space),
+            Ok(_) => continue,
+            Err(err) => Err(err),
+        };
+
+        if frames.send(frame).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use super::*;
+
+/// What a [`Plane`] of a video frame holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlaneKind {
+    /// All components of a pixel interleaved, as in [`FourCCVideoType::UYVY`] or [`FourCCVideoType::BGRA`]
+    Packed,
+    /// The luma (Y) component
+    Y,
+    /// The blue difference chroma (U or Cb) component
+    U,
+    /// The red difference chroma (V or Cr) component
+    V,
+    /// Both chroma components interleaved, U first
+    UV,
+    /// The alpha component
+    Alpha,
+}
+
+/// The position of a plane within the buffer of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PlaneLayout {
+    pub kind: PlaneKind,
+    pub offset: usize,
+    pub width: usize,
+    pub height: usize,
+    pub line_stride: usize,
+    pub bytes_per_pixel: usize,
+}
+
+impl PlaneLayout {
+    fn size(&self) -> usize {
+        self.line_stride * self.height
+    }
+}
+
+impl FourCCVideoType {
+    /// The line stride in bytes of a packed frame `width` pixels wide
+    ///
+    /// This is the stride the SDK assumes when a frame has a line stride of 0.
+    pub fn packed_line_stride(self, width: usize) -> usize {
+        match self {
+            FourCCVideoType::UYVY | FourCCVideoType::UYVA => width * 2,
+            FourCCVideoType::P216 | FourCCVideoType::PA16 => width * 2,
+            FourCCVideoType::YV12 | FourCCVideoType::I420 | FourCCVideoType::NV12 => width,
+            FourCCVideoType::BGRA
+            | FourCCVideoType::BGRX
+            | FourCCVideoType::RGBA
+            | FourCCVideoType::RGBX => width * 4,
+        }
+    }
+
+    /// The size in bytes of all planes of a frame `height` lines high
+    pub fn buffer_size(self, line_stride: usize, height: usize) -> usize {
+        self.plane_layouts(0, height, line_stride)
+            .iter()
+            .map(PlaneLayout::size)
+            .sum()
+    }
+
+    /// The planes of a frame, in the order they are laid out in memory
+    pub(crate) fn plane_layouts(
+        self,
+        width: usize,
+        height: usize,
+        line_stride: usize,
+    ) -> Vec<PlaneLayout> {
+        let plane = |kind, width, height, line_stride, bytes_per_pixel| PlaneLayout {
+            kind,
+            offset: 0,
+            width,
+            height,
+            line_stride,
+            bytes_per_pixel,
+        };
+
+        let planes = match self {
+            FourCCVideoType::UYVY => vec![plane(PlaneKind::Packed, width, height, line_stride, 2)],
+            FourCCVideoType::BGRA
+            | FourCCVideoType::BGRX
+            | FourCCVideoType::RGBA
+            | FourCCVideoType::RGBX => {
+                vec![plane(PlaneKind::Packed, width, height, line_stride, 4)]
+            }
+            // the alpha plane has one byte per pixel
+            FourCCVideoType::UYVA => vec![
+                plane(PlaneKind::Packed, width, height, line_stride, 2),
+                plane(PlaneKind::Alpha, width, height, line_stride / 2, 1),
+            ],
+            FourCCVideoType::P216 => vec![
+                plane(PlaneKind::Y, width, height, line_stride, 2),
+                plane(PlaneKind::UV, width / 2, height, line_stride, 4),
+            ],
+            FourCCVideoType::PA16 => vec![
+                plane(PlaneKind::Y, width, height, line_stride, 2),
+                plane(PlaneKind::UV, width / 2, height, line_stride, 4),
+                plane(PlaneKind::Alpha, width, height, line_stride, 2),
+            ],
+            FourCCVideoType::YV12 => vec![
+                plane(PlaneKind::Y, width, height, line_stride, 1),
+                plane(PlaneKind::U, width / 2, height / 2, line_stride / 2, 1),
+                plane(PlaneKind::V, width / 2, height / 2, line_stride / 2, 1),
+            ],
+            FourCCVideoType::I420 => vec![
+                plane(PlaneKind::Y, width, height, line_stride, 1),
+                plane(PlaneKind::V, width / 2, height / 2, line_stride / 2, 1),
+                plane(PlaneKind::U, width / 2, height / 2, line_stride / 2, 1),
+            ],
+            FourCCVideoType::NV12 => vec![
+                plane(PlaneKind::Y, width, height, line_stride, 1),
+                plane(PlaneKind::UV, width / 2, height / 2, line_stride, 2),
+            ],
+        };
+
+        let mut offset = 0;
+        planes
+            .into_iter()
+            .map(|mut plane| {
+                plane.offset = offset;
+                offset += plane.size();
+                plane
+            })
+            .collect()
+    }
+}
+
+/// A view of one plane of the pixel data of a [`VideoData`]
+///
+/// The layout of the planes for each [`FourCCVideoType`] follows the SDK documentation.
+#[derive(Debug, Clone, Copy)]
+pub struct Plane<'a> {
+    kind: PlaneKind,
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    line_stride_in_bytes: u32,
+    bytes_per_pixel: u32,
+}
+
+impl<'a> Plane<'a> {
+    pub(crate) fn new(layout: &PlaneLayout, data: &'a [u8]) -> Self {
+        Self {
+            kind: layout.kind,
+            data: &data[layout.offset..layout.offset + layout.size()],
+            width: layout.width as _,
+            height: layout.height as _,
+            line_stride_in_bytes: layout.line_stride as _,
+            bytes_per_pixel: layout.bytes_per_pixel as _,
+        }
+    }
+
+    /// What this plane holds
+    pub fn kind(&self) -> PlaneKind {
+        self.kind
+    }
+
+    /// All lines of the plane, including any padding at the end of each line
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// The number of pixels in each line of the plane
+    ///
+    /// For subsampled chroma planes this is less than the width of the frame.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The number of lines of the plane
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The number of bytes from the start of one line to the start of the next
+    pub fn line_stride_in_bytes(&self) -> u32 {
+        self.line_stride_in_bytes
+    }
+
+    /// The number of bytes of a single pixel of this plane
+    ///
+    /// 16 bit samples are stored in native endianness,
+    /// and a pixel of a [`PlaneKind::UV`] plane holds both components.
+    pub fn bytes_per_pixel(&self) -> u32 {
+        self.bytes_per_pixel
+    }
+
+    /// The pixels of line `y`, without padding
+    ///
+    /// Returns `None` if `y` is out of range.
+    pub fn line(&self, y: u32) -> Option<&'a [u8]> {
+        if y >= self.height {
+            return None;
+        }
+        let start = y as usize * self.line_stride_in_bytes as usize;
+        let len = self.width as usize * self.bytes_per_pixel as usize;
+        self.data.get(start..start + len)
+    }
+}
+
+#[test]
+fn plane_layouts() {
+    let planes = FourCCVideoType::I420.plane_layouts(16, 8, 16);
+    let kinds: Vec<_> = planes.iter().map(|p| p.kind).collect();
+    assert_eq!(kinds, [PlaneKind::Y, PlaneKind::V, PlaneKind::U]);
+    assert_eq!(planes[2].offset, 16 * 8 + 8 * 4);
+    assert_eq!(FourCCVideoType::I420.buffer_size(16, 8), 16 * 12);
+
+    let data = vec![0; FourCCVideoType::PA16.buffer_size(32, 4)];
+    let layout = FourCCVideoType::PA16.plane_layouts(16, 4, 32);
+    let alpha = Plane::new(&layout[2], &data);
+    assert_eq!(alpha.kind(), PlaneKind::Alpha);
+    assert_eq!(alpha.data().len(), 32 * 4);
+    assert_eq!(alpha.line(3).map(<[u8]>::len), Some(32));
+    assert!(alpha.line(4).is_none());
+}
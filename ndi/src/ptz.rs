@@ -0,0 +1,259 @@
+use super::*;
+
+/// White balance modes for a PTZ camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WhiteBalance {
+    /// Put the camera in auto white balance mode
+    Auto,
+    /// Put the camera in indoor white balance
+    Indoor,
+    /// Put the camera in outdoor white balance
+    Outdoor,
+    /// Use the current brightness to automatically set the current white balance
+    OneShot,
+    /// Set the manual camera white balance using the R, B values
+    Manual {
+        /// 0.0 (not red) ... 1.0 (very red)
+        red: f32,
+        /// 0.0 (not blue) ... 1.0 (very blue)
+        blue: f32,
+    },
+}
+
+/// Exposure modes for a PTZ camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exposure {
+    /// Put the camera in auto-exposure mode
+    Auto,
+    /// Manually set the camera exposure iris
+    ///
+    /// 0.0 (dark) ... 1.0 (light)
+    Level(f32),
+    /// Manually set the camera exposure parameters
+    Manual {
+        /// 0.0 (dark) ... 1.0 (light)
+        iris: f32,
+        /// 0.0 (dark) ... 1.0 (light)
+        gain: f32,
+        /// 0.0 (slow) ... 1.0 (fast)
+        shutter_speed: f32,
+    },
+}
+
+/// Highest preset number accepted by [`PtzControl::store_preset()`] and [`PtzControl::recall_preset()`]
+pub const PTZ_MAX_PRESET: u8 = 99;
+
+/// Controls a PTZ camera connected to a [`Recv`]
+///
+/// Obtained through [`Recv::ptz()`]. Every command first checks whether the connected source
+/// is PTZ capable and returns [`Error::NotSupported`] if it is not.
+/// Note that it might take a second or two after the connection for PTZ support to be reported;
+/// [`Recv::capture_all()`] returns [`FrameType::StatusChange`] when this might have changed.
+///
+/// All values are range checked before they are passed to the SDK,
+/// out of range values return [`Error::OutOfRange`].
+pub struct PtzControl<'a> {
+    recv: &'a Recv,
+}
+
+impl<'a> PtzControl<'a> {
+    pub(crate) fn new(recv: &'a Recv) -> Self {
+        Self { recv }
+    }
+
+    /// Whether the connected source is a PTZ camera
+    pub fn is_supported(&self) -> bool {
+        let _lock = self.recv.guard.lock().unwrap();
+        unsafe { NDIlib_recv_ptz_is_supported(**self.recv.p_instance) }
+    }
+
+    fn command(
+        &self,
+        name: &'static str,
+        f: impl FnOnce(NDIlib_recv_instance_t) -> bool,
+    ) -> Result<(), Error> {
+        let _lock = self.recv.guard.lock().unwrap();
+        let p_instance = **self.recv.p_instance;
+        if !unsafe { NDIlib_recv_ptz_is_supported(p_instance) } {
+            return Err(Error::NotSupported("PTZ"));
+        }
+
+        if f(p_instance) {
+            Ok(())
+        } else {
+            Err(Error::CommandFailed(name))
+        }
+    }
+
+    /// Zoom to an absolute value.
+    ///
+    /// 0.0 (zoomed in) ... 1.0 (zoomed out)
+    pub fn zoom(&self, zoom_value: f32) -> Result<(), Error> {
+        let zoom_value = check_range(zoom_value, 0.0, 1.0, "zoom_value")?;
+        self.command("ptz_zoom", |p| unsafe {
+            NDIlib_recv_ptz_zoom(p, zoom_value)
+        })
+    }
+
+    /// Zoom at a particular speed
+    ///
+    /// -1.0 (zoom outwards) ... +1.0 (zoom inwards)
+    pub fn zoom_speed(&self, zoom_speed: f32) -> Result<(), Error> {
+        let zoom_speed = check_range(zoom_speed, -1.0, 1.0, "zoom_speed")?;
+        self.command("ptz_zoom_speed", |p| unsafe {
+            NDIlib_recv_ptz_zoom_speed(p, zoom_speed)
+        })
+    }
+
+    /// Set the pan and tilt to an absolute value
+    ///
+    /// - `pan_value`: -1.0 (left) ... 0.0 (centered) ... +1.0 (right)
+    /// - `tilt_value`: -1.0 (bottom) ... 0.0 (centered) ... +1.0 (top)
+    pub fn pan_tilt(&self, pan_value: f32, tilt_value: f32) -> Result<(), Error> {
+        let pan_value = check_range(pan_value, -1.0, 1.0, "pan_value")?;
+        let tilt_value = check_range(tilt_value, -1.0, 1.0, "tilt_value")?;
+        self.command("ptz_pan_tilt", |p| unsafe {
+            NDIlib_recv_ptz_pan_tilt(p, pan_value, tilt_value)
+        })
+    }
+
+    /// Set the pan and tilt direction and speed
+    ///
+    /// - `pan_speed`: -1.0 (moving right) ... 0.0 (stopped) ... +1.0 (moving left)
+    /// - `tilt_speed`: -1.0 (down) ... 0.0 (stopped) ... +1.0 (moving up)
+    pub fn pan_tilt_speed(&self, pan_speed: f32, tilt_speed: f32) -> Result<(), Error> {
+        let pan_speed = check_range(pan_speed, -1.0, 1.0, "pan_speed")?;
+        let tilt_speed = check_range(tilt_speed, -1.0, 1.0, "tilt_speed")?;
+        self.command("ptz_pan_tilt_speed", |p| unsafe {
+            NDIlib_recv_ptz_pan_tilt_speed(p, pan_speed, tilt_speed)
+        })
+    }
+
+    /// Store the current position, focus, etc... as a preset.
+    ///
+    /// `preset_no` must be in `0..=`[`PTZ_MAX_PRESET`]
+    pub fn store_preset(&self, preset_no: u8) -> Result<(), Error> {
+        let preset_no = check_preset(preset_no)?;
+        self.command("ptz_store_preset", |p| unsafe {
+            NDIlib_recv_ptz_store_preset(p, preset_no)
+        })
+    }
+
+    /// Recall a preset, including position, focus, etc...
+    ///
+    /// - `preset_no` must be in `0..=`[`PTZ_MAX_PRESET`]
+    /// - `speed`: 0.0 (as slow as possible) ... 1.0 (as fast as possible)
+    pub fn recall_preset(&self, preset_no: u8, speed: f32) -> Result<(), Error> {
+        let preset_no = check_preset(preset_no)?;
+        let speed = check_range(speed, 0.0, 1.0, "speed")?;
+        self.command("ptz_recall_preset", |p| unsafe {
+            NDIlib_recv_ptz_recall_preset(p, preset_no, speed)
+        })
+    }
+
+    /// Put the camera in auto-focus
+    pub fn auto_focus(&self) -> Result<(), Error> {
+        self.command("ptz_auto_focus", |p| unsafe {
+            NDIlib_recv_ptz_auto_focus(p)
+        })
+    }
+
+    /// Focus to an absolute value.
+    ///
+    /// 0.0 (focused to infinity) ... 1.0 (focused as close as possible)
+    pub fn focus(&self, focus_value: f32) -> Result<(), Error> {
+        let focus_value = check_range(focus_value, 0.0, 1.0, "focus_value")?;
+        self.command("ptz_focus", |p| unsafe {
+            NDIlib_recv_ptz_focus(p, focus_value)
+        })
+    }
+
+    /// Focus at a particular speed
+    ///
+    /// -1.0 (focus outwards) ... +1.0 (focus inwards)
+    pub fn focus_speed(&self, focus_speed: f32) -> Result<(), Error> {
+        let focus_speed = check_range(focus_speed, -1.0, 1.0, "focus_speed")?;
+        self.command("ptz_focus_speed", |p| unsafe {
+            NDIlib_recv_ptz_focus_speed(p, focus_speed)
+        })
+    }
+
+    /// Set the white balance mode of the camera
+    pub fn white_balance(&self, white_balance: WhiteBalance) -> Result<(), Error> {
+        match white_balance {
+            WhiteBalance::Auto => self.command("ptz_white_balance_auto", |p| unsafe {
+                NDIlib_recv_ptz_white_balance_auto(p)
+            }),
+            WhiteBalance::Indoor => self.command("ptz_white_balance_indoor", |p| unsafe {
+                NDIlib_recv_ptz_white_balance_indoor(p)
+            }),
+            WhiteBalance::Outdoor => self.command("ptz_white_balance_outdoor", |p| unsafe {
+                NDIlib_recv_ptz_white_balance_outdoor(p)
+            }),
+            WhiteBalance::OneShot => self.command("ptz_white_balance_oneshot", |p| unsafe {
+                NDIlib_recv_ptz_white_balance_oneshot(p)
+            }),
+            WhiteBalance::Manual { red, blue } => {
+                let red = check_range(red, 0.0, 1.0, "red")?;
+                let blue = check_range(blue, 0.0, 1.0, "blue")?;
+                self.command("ptz_white_balance_manual", |p| unsafe {
+                    NDIlib_recv_ptz_white_balance_manual(p, red, blue)
+                })
+            }
+        }
+    }
+
+    /// Set the exposure mode of the camera
+    pub fn exposure(&self, exposure: Exposure) -> Result<(), Error> {
+        match exposure {
+            Exposure::Auto => self.command("ptz_exposure_auto", |p| unsafe {
+                NDIlib_recv_ptz_exposure_auto(p)
+            }),
+            Exposure::Level(exposure_level) => {
+                let exposure_level = check_range(exposure_level, 0.0, 1.0, "exposure_level")?;
+                self.command("ptz_exposure_manual", |p| unsafe {
+                    NDIlib_recv_ptz_exposure_manual(p, exposure_level)
+                })
+            }
+            Exposure::Manual {
+                iris,
+                gain,
+                shutter_speed,
+            } => {
+                let iris = check_range(iris, 0.0, 1.0, "iris")?;
+                let gain = check_range(gain, 0.0, 1.0, "gain")?;
+                let shutter_speed = check_range(shutter_speed, 0.0, 1.0, "shutter_speed")?;
+                self.command("ptz_exposure_manual_v2", |p| unsafe {
+                    NDIlib_recv_ptz_exposure_manual_v2(p, iris, gain, shutter_speed)
+                })
+            }
+        }
+    }
+}
+
+fn check_range(value: f32, min: f32, max: f32, name: &'static str) -> Result<f32, Error> {
+    // written this way so that NaN is rejected as well
+    if value >= min && value <= max {
+        Ok(value)
+    } else {
+        Err(Error::OutOfRange(value, name))
+    }
+}
+
+fn check_preset(preset_no: u8) -> Result<i32, Error> {
+    if preset_no <= PTZ_MAX_PRESET {
+        Ok(preset_no as _)
+    } else {
+        Err(Error::OutOfRange(preset_no as _, "preset_no"))
+    }
+}
+
+#[test]
+fn ptz_range_check() {
+    assert!(check_range(0.5, 0.0, 1.0, "zoom").is_ok());
+    assert!(check_range(-1.0, -1.0, 1.0, "pan").is_ok());
+    assert!(check_range(1.01, 0.0, 1.0, "zoom").is_err());
+    assert!(check_range(f32::NAN, 0.0, 1.0, "zoom").is_err());
+    assert!(check_preset(99).is_ok());
+    assert!(check_preset(100).is_err());
+}
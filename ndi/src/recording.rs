@@ -0,0 +1,176 @@
+use super::*;
+use std::{ffi::CString, mem::MaybeUninit, time::Duration};
+
+/// Frame count and time span of the current recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecordingTimes {
+    /// The number of actual video frames recorded.
+    pub no_frames: i64,
+    /// The starting time of the recording, in UTC time, at 100 ns unit intervals.
+    pub start_time: i64,
+    /// The current largest time of the recording, in UTC time, at 100 ns unit intervals.
+    pub last_time: i64,
+}
+
+impl RecordingTimes {
+    fn from_binding(times: NDIlib_recv_recording_time_t) -> Self {
+        Self {
+            no_frames: times.no_frames,
+            start_time: times.start_time,
+            last_time: times.last_time,
+        }
+    }
+
+    /// The length of the recording irrespective of frame-rate
+    pub fn duration(&self) -> Duration {
+        let intervals = (self.last_time - self.start_time).max(0) as u64;
+        Duration::from_nanos(intervals * 100)
+    }
+}
+
+/// A snapshot of the recording state of a source
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordingStatus {
+    /// Whether the source is currently recording.
+    ///
+    /// Because there is one recorder and multiple receivers might be connected to it,
+    /// the recording might have been initiated by someone else.
+    pub is_recording: bool,
+    /// The filename of the current or last recording.
+    ///
+    /// This is a UNC path when one is available, otherwise the local filename on the recording machine.
+    pub filename: Option<String>,
+    /// The recording error, if any
+    pub error: Option<String>,
+    /// The frame count and times of the recording, if available
+    pub times: Option<RecordingTimes>,
+}
+
+/// Controls recording on the source connected to a [`Recv`]
+///
+/// Obtained through [`Recv::recording()`]. Every command first checks whether the connected source
+/// supports recording and returns [`Error::NotSupported`] if it does not.
+/// Note that it might take a second or two after the connection for recording support to be reported;
+/// [`Recv::capture_all()`] returns [`FrameType::StatusChange`] when this might have changed.
+///
+/// This functionality is deprecated in the NDI SDK in favor of the external recording application
+/// shipped with it, but is still supported by NDI|HX devices.
+pub struct RecordingControl<'a> {
+    recv: &'a Recv,
+}
+
+impl<'a> RecordingControl<'a> {
+    pub(crate) fn new(recv: &'a Recv) -> Self {
+        Self { recv }
+    }
+
+    /// Whether the connected source supports recording
+    pub fn is_supported(&self) -> bool {
+        let _lock = self.recv.guard.lock().unwrap();
+        unsafe { NDIlib_recv_recording_is_supported(**self.recv.p_instance) }
+    }
+
+    fn query<T>(&self, f: impl FnOnce(NDIlib_recv_instance_t) -> T) -> Result<T, Error> {
+        let _lock = self.recv.guard.lock().unwrap();
+        let p_instance = **self.recv.p_instance;
+        if !unsafe { NDIlib_recv_recording_is_supported(p_instance) } {
+            return Err(Error::NotSupported("recording"));
+        }
+
+        Ok(f(p_instance))
+    }
+
+    fn command(
+        &self,
+        name: &'static str,
+        f: impl FnOnce(NDIlib_recv_instance_t) -> bool,
+    ) -> Result<(), Error> {
+        if self.query(f)? {
+            Ok(())
+        } else {
+            Err(Error::CommandFailed(name))
+        }
+    }
+
+    /// Start recording.
+    ///
+    /// If the recorder was already recording then the message is ignored.
+    /// A filename is passed in as a "hint". Since the recorder might already be recording
+    /// (or might not allow complete flexibility over its filename), the filename might or might not be used.
+    /// If the filename is `None` or empty, a name will be chosen automatically.
+    pub fn start(&self, filename_hint: Option<&str>) -> Result<(), Error> {
+        let filename_hint = match filename_hint {
+            Some(hint) => {
+                Some(CString::new(hint).map_err(|_| Error::InvalidString("filename_hint"))?)
+            }
+            None => None,
+        };
+        let p_filename_hint = filename_hint.as_ref().map_or(null(), |s| s.as_ptr());
+
+        self.command("recording_start", |p| unsafe {
+            NDIlib_recv_recording_start(p, p_filename_hint)
+        })
+    }
+
+    /// Stop recording.
+    pub fn stop(&self) -> Result<(), Error> {
+        self.command("recording_stop", |p| unsafe {
+            NDIlib_recv_recording_stop(p)
+        })
+    }
+
+    /// Control the audio level for the recording.
+    ///
+    /// `level_db` is specified in decibels relative to the reference level of the source.
+    /// Not all recording sources support controlling audio levels.
+    pub fn set_audio_level(&self, level_db: f32) -> Result<(), Error> {
+        if !level_db.is_finite() {
+            return Err(Error::OutOfRange(level_db, "level_db"));
+        }
+
+        self.command("recording_set_audio_level", |p| unsafe {
+            NDIlib_recv_recording_set_audio_level(p, level_db)
+        })
+    }
+
+    /// Whether the source is currently recording
+    pub fn is_recording(&self) -> Result<bool, Error> {
+        self.query(|p| unsafe { NDIlib_recv_recording_is_recording(p) })
+    }
+
+    /// The filename of the current or last recording
+    ///
+    /// This remains valid even after the file has stopped being recorded until the next file is started.
+    pub fn filename(&self) -> Result<Option<String>, Error> {
+        let p_filename = self.query(|p| unsafe { NDIlib_recv_recording_get_filename(p) })?;
+        Ok(self.recv.take_string(p_filename))
+    }
+
+    /// The recording error, `None` if there is no error
+    pub fn error(&self) -> Result<Option<String>, Error> {
+        let p_error = self.query(|p| unsafe { NDIlib_recv_recording_get_error(p) })?;
+        Ok(self.recv.take_string(p_error))
+    }
+
+    /// The frame count and times of the current recording, `None` if they are not available
+    pub fn times(&self) -> Result<Option<RecordingTimes>, Error> {
+        self.query(|p| {
+            let mut times = MaybeUninit::<NDIlib_recv_recording_time_t>::uninit();
+            if unsafe { NDIlib_recv_recording_get_times(p, times.as_mut_ptr()) } {
+                Some(RecordingTimes::from_binding(unsafe { times.assume_init() }))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Poll the complete recording state of the source
+    pub fn status(&self) -> Result<RecordingStatus, Error> {
+        Ok(RecordingStatus {
+            is_recording: self.is_recording()?,
+            filename: self.filename()?,
+            error: self.error()?,
+            times: self.times()?,
+        })
+    }
+}
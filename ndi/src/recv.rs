@@ -1,5 +1,5 @@
 use super::*;
-use std::{ffi::CString, fmt::Display, mem, sync::Mutex};
+use std::{ffi::CString, fmt::Display, mem, os::raw::c_char, sync::Mutex, thread, time::Duration};
 
 /// Current performance levels of the receiving.
 ///
@@ -109,6 +109,9 @@ impl RecvBuilder {
     }
 
     /// Choose the [`Source`] to connect to
+    ///
+    /// This can be a source found with [`Find`], or one created with [`Source::from_name()`]
+    /// or [`Source::from_address()`].
     pub fn source_to_connect_to(mut self, source: Source) -> Self {
         self.source_to_connect_to = Some(source);
         self
@@ -146,18 +149,20 @@ impl RecvBuilder {
     }
 
     /// Build the [`Recv`]
-    pub fn build(self) -> Result<Recv, RecvCreateError> {
+    pub fn build(self, ndi: &Ndi) -> Result<Recv, Error> {
         // From default C++ constructor in Processing.NDI.Recv.h
         let mut settings: NDIlib_recv_create_v3_t = NDIlib_recv_create_v3_t {
-            source_to_connect_to: Source::new().p_instance,
+            source_to_connect_to: Source::default().to_binding(),
             color_format: RecvColorFormat::UYVY_BGRA as _,
             bandwidth: RecvBandwidth::Highest as _,
             allow_video_fields: true,
             p_ndi_recv_name: null(),
         };
 
-        if let Some(src) = self.source_to_connect_to {
-            settings.source_to_connect_to = src.p_instance;
+        // the source has to live until the receiver is created
+        let source_to_connect_to = self.source_to_connect_to;
+        if let Some(src) = &source_to_connect_to {
+            settings.source_to_connect_to = src.to_binding();
         }
         if let Some(color_format) = self.color_format {
             settings.color_format = color_format as _;
@@ -169,13 +174,13 @@ impl RecvBuilder {
             settings.allow_video_fields = allow_video_fields;
         }
         if let Some(ndi_recv_name) = self.ndi_recv_name {
-            // String shouldn't contain a 0-byte
-            let cstr = CString::new(ndi_recv_name).unwrap();
+            let cstr =
+                CString::new(ndi_recv_name).map_err(|_| Error::InvalidString("ndi_recv_name"))?;
 
             settings.p_ndi_recv_name = cstr.into_raw();
         }
 
-        Recv::with_settings(settings)
+        Recv::with_settings(settings, ndi)
     }
 }
 
@@ -209,29 +214,203 @@ impl RecvQueueSize {
     }
 }
 
+/// The settings of the device connected to a [`Recv`]
+///
+/// Returned by [`Recv::device_status()`]. These might change when [`FrameType::StatusChange`] is captured,
+/// use [`DeviceStatus::changes()`] to find out what changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DeviceStatus {
+    /// The URL of the web control interface of the device, if it has one.
+    ///
+    /// This is a fully formed URL, for instance "http://10.28.1.192/configuration/"
+    pub web_control: Option<String>,
+    /// Whether the device is a PTZ camera, see [`PtzControl`]
+    pub ptz_supported: bool,
+    /// Whether the device supports recording, see [`RecordingControl`]
+    pub recording_supported: bool,
+    /// The current number of sources connected to
+    pub no_connections: u32,
+}
+
+impl DeviceStatus {
+    /// Compare against a previous status to find out which fields have changed
+    pub fn changes(&self, previous: &DeviceStatus) -> DeviceStatusChanges {
+        DeviceStatusChanges {
+            web_control: self.web_control != previous.web_control,
+            ptz_supported: self.ptz_supported != previous.ptz_supported,
+            recording_supported: self.recording_supported != previous.recording_supported,
+            no_connections: self.no_connections != previous.no_connections,
+        }
+    }
+}
+
+/// Which fields of a [`DeviceStatus`] have changed, see [`DeviceStatus::changes()`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DeviceStatusChanges {
+    /// [`DeviceStatus::web_control`] changed
+    pub web_control: bool,
+    /// [`DeviceStatus::ptz_supported`] changed
+    pub ptz_supported: bool,
+    /// [`DeviceStatus::recording_supported`] changed
+    pub recording_supported: bool,
+    /// [`DeviceStatus::no_connections`] changed
+    pub no_connections: bool,
+}
+
+impl DeviceStatusChanges {
+    /// Whether any field has changed
+    pub fn any(&self) -> bool {
+        self.web_control || self.ptz_supported || self.recording_supported || self.no_connections
+    }
+}
+
+/// A frame received by a [`Recv`], see [`Recv::capture()`]
+pub enum Frame {
+    /// A video frame
+    Video(VideoData),
+    /// An audio frame
+    Audio(AudioData),
+    /// A metadata frame
+    Metadata(MetaData),
+    /// The settings of the source have changed, see [`FrameType::StatusChange`]
+    StatusChange,
+    /// Nothing was received within the timeout
+    Timeout,
+}
+
+impl Frame {
+    /// The [`FrameType`] of this frame
+    pub fn frame_type(&self) -> FrameType {
+        match self {
+            Frame::Video(_) => FrameType::Video,
+            Frame::Audio(_) => FrameType::Audio,
+            Frame::Metadata(_) => FrameType::Metadata,
+            Frame::StatusChange => FrameType::StatusChange,
+            Frame::Timeout => FrameType::None,
+        }
+    }
+}
+
+/// The kinds of frames to capture with [`Recv::capture_filtered()`]
+///
+/// Filters can be combined with `|`, for instance `FrameFilter::VIDEO | FrameFilter::AUDIO`.
+/// Status changes are always reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameFilter {
+    /// Capture video frames
+    pub video: bool,
+    /// Capture audio frames
+    pub audio: bool,
+    /// Capture metadata frames
+    pub metadata: bool,
+}
+
+impl FrameFilter {
+    /// Capture nothing but status changes
+    pub const NONE: Self = Self {
+        video: false,
+        audio: false,
+        metadata: false,
+    };
+    /// Capture video frames
+    pub const VIDEO: Self = Self {
+        video: true,
+        ..Self::NONE
+    };
+    /// Capture audio frames
+    pub const AUDIO: Self = Self {
+        audio: true,
+        ..Self::NONE
+    };
+    /// Capture metadata frames
+    pub const METADATA: Self = Self {
+        metadata: true,
+        ..Self::NONE
+    };
+    /// Capture every kind of frame
+    pub const ALL: Self = Self {
+        video: true,
+        audio: true,
+        metadata: true,
+    };
+}
+
+impl Default for FrameFilter {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl std::ops::BitOr for FrameFilter {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            video: self.video || rhs.video,
+            audio: self.audio || rhs.audio,
+            metadata: self.metadata || rhs.metadata,
+        }
+    }
+}
+
+/// A blocking iterator over the frames of a [`Recv`], see [`Recv::frames()`]
+///
+/// A lost connection is yielded once as [`Error::Disconnected`],
+/// after which the iterator waits for frames to be received again.
+pub struct Frames<'a> {
+    recv: &'a Recv,
+    filter: FrameFilter,
+    disconnected: bool,
+}
+
+impl Iterator for Frames<'_> {
+    type Item = Result<Frame, Error>;
+
+    /// Wait for the next frame, this never returns `None` or [`Frame::Timeout`]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.recv.capture_filtered(self.filter, 1000) {
+                Ok(Frame::Timeout) => continue,
+                Err(Error::Disconnected) if self.disconnected => {
+                    // already reported, back off until the source is back
+                    thread::sleep(Duration::from_millis(100));
+                }
+                Err(Error::Disconnected) => {
+                    self.disconnected = true;
+                    return Some(Err(Error::Disconnected));
+                }
+                frame => {
+                    self.disconnected = false;
+                    return Some(frame);
+                }
+            }
+        }
+    }
+}
+
 /// The NDI receiver struct
 pub struct Recv {
     /// whether the Recv is currently connected
     pub connected: bool,
-    p_instance: Arc<OnDrop<NDIlib_recv_instance_t>>,
-    guard: Mutex<()>,
+    pub(crate) p_instance: Arc<OnDrop<NDIlib_recv_instance_t>>,
+    pub(crate) guard: Mutex<()>,
 }
 
 unsafe impl core::marker::Send for Recv {}
 unsafe impl core::marker::Sync for Recv {}
 
 impl Recv {
-    fn with_settings(settings: NDIlib_recv_create_v3_t) -> Result<Self, RecvCreateError> {
+    fn with_settings(settings: NDIlib_recv_create_v3_t, ndi: &Ndi) -> Result<Self, Error> {
         let p_instance = unsafe { NDIlib_recv_create_v3(&settings) };
         if p_instance.is_null() {
-            return Err(RecvCreateError);
+            return Err(Error::CreateFailed("Recv"));
         }
 
         let guard = Mutex::new(());
         let mut this = Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_recv_destroy(s)
-            })),
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_recv_destroy(s) }).retain(ndi),
+            ),
             connected: false,
             guard,
         };
@@ -243,18 +422,18 @@ impl Recv {
     /// Create new receiver which isn't connected to any sources
     ///
     /// It is recommended that you use [`RecvBuilder`] instead if possible
-    pub fn new() -> Result<Self, RecvCreateError> {
+    pub fn new(ndi: &Ndi) -> Result<Self, Error> {
         let p_instance = unsafe { NDIlib_recv_create_v3(null()) };
 
         if p_instance.is_null() {
-            return Err(RecvCreateError);
+            return Err(Error::CreateFailed("Recv"));
         }
 
         let guard = Mutex::new(());
         Ok(Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_recv_destroy(s)
-            })),
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_recv_destroy(s) }).retain(ndi),
+            ),
             connected: false,
             guard,
         })
@@ -262,8 +441,8 @@ impl Recv {
 
     /// Connect to a source
     pub fn connect(&mut self, source: &Source) {
-        let instance: *const NDIlib_source_t = &source.p_instance;
-        unsafe { NDIlib_recv_connect(**self.p_instance, instance) };
+        let instance = source.to_binding();
+        unsafe { NDIlib_recv_connect(**self.p_instance, &instance) };
     }
 
     /// Disconnect from all sources
@@ -278,7 +457,8 @@ impl Recv {
     /// This call can be called simultaneously on separate threads,
     /// so it is entirely possible to receive audio, video, metadata all on separate threads.
     /// This function will return [`FrameType::None`] if no data is received within the specified timeout
-    /// and [`FrameType::ErrorFrame`] if the connection is lost.
+    /// and [`Error::Disconnected`] if the connection is lost.
+    /// Only the frame matching the returned [`FrameType`] is replaced.
     /// Buffers captured with this must be freed with the appropriate free function.
     pub fn capture_all(
         &self,
@@ -286,24 +466,12 @@ impl Recv {
         audio_data: &mut Option<AudioData>,
         meta_data: &mut Option<MetaData>,
         timeout_ms: u32,
-    ) -> FrameType {
-        let mut video = if let Some(x) = video_data {
-            mem::MaybeUninit::new(x.p_instance)
-        } else {
-            mem::MaybeUninit::zeroed()
-        };
+    ) -> Result<FrameType, Error> {
+        let mut video = mem::MaybeUninit::zeroed();
 
-        let mut audio = if let Some(x) = audio_data {
-            mem::MaybeUninit::new(x.p_instance)
-        } else {
-            mem::MaybeUninit::zeroed()
-        };
+        let mut audio = mem::MaybeUninit::zeroed();
 
-        let mut metadata = if let Some(x) = meta_data {
-            mem::MaybeUninit::new(x.p_instance)
-        } else {
-            mem::MaybeUninit::zeroed()
-        };
+        let mut metadata = mem::MaybeUninit::zeroed();
 
         let response = unsafe {
             NDIlib_recv_capture_v3(
@@ -315,39 +483,74 @@ impl Recv {
             )
         };
 
-        if !video.as_ptr().is_null() {
-            *video_data = Some(VideoData::from_binding_recv(
-                self.p_instance.clone(),
-                unsafe { video.assume_init() },
-            ));
+        // only the frame of the returned type has been filled in
+        let frame_type = FrameType::from_capture(response)?;
+        match frame_type {
+            FrameType::Video => {
+                *video_data = Some(VideoData::from_binding_recv(
+                    self.p_instance.clone(),
+                    unsafe { video.assume_init() },
+                ));
+            }
+            FrameType::Audio => {
+                *audio_data = Some(AudioData::from_binding_recv(
+                    self.p_instance.clone(),
+                    unsafe { audio.assume_init() },
+                ));
+            }
+            FrameType::Metadata => {
+                *meta_data = Some(MetaData::from_binding_recv(
+                    self.p_instance.clone(),
+                    unsafe { metadata.assume_init() },
+                ));
+            }
+            _ => {}
         }
 
-        if !audio.as_ptr().is_null() {
-            *audio_data = Some(AudioData::from_binding_recv(
-                self.p_instance.clone(),
-                unsafe { audio.assume_init() },
-            ));
-        }
+        Ok(frame_type)
+    }
 
-        if !metadata.as_ptr().is_null() {
-            *meta_data = Some(MetaData::from_binding_recv(
-                self.p_instance.clone(),
-                unsafe { metadata.assume_init() },
-            ));
-        }
+    /// Receive the next video, audio or metadata frame, or a status change
+    ///
+    /// Returns [`Frame::Timeout`] if nothing is received within `timeout_ms`
+    /// and [`Error::Disconnected`] if the connection is lost.
+    /// The frames are freed when they are dropped.
+    pub fn capture(&self, timeout_ms: u32) -> Result<Frame, Error> {
+        self.capture_filtered(FrameFilter::ALL, timeout_ms)
+    }
 
-        FrameType::try_from(response).unwrap()
+    /// Receive the next frame of the kinds in `filter`, or a status change
+    ///
+    /// This can be called simultaneously on separate threads for different kinds of frames,
+    /// see [`Recv::capture()`] for the rest.
+    pub fn capture_filtered(&self, filter: FrameFilter, timeout_ms: u32) -> Result<Frame, Error> {
+        capture_frame(&self.p_instance, filter, timeout_ms)
+    }
+
+    /// A blocking iterator over all frames received, skipping timeouts
+    pub fn frames(&self) -> Frames<'_> {
+        self.frames_filtered(FrameFilter::ALL)
+    }
+
+    /// A blocking iterator over the frames of the kinds in `filter`, skipping timeouts
+    pub fn frames_filtered(&self, filter: FrameFilter) -> Frames<'_> {
+        Frames {
+            recv: self,
+            filter,
+            disconnected: false,
+        }
     }
 
     /// Receive video frame
-    pub fn capture_video(&self, video_data: &mut Option<VideoData>, timeout_ms: u32) -> FrameType {
+    ///
+    /// `video_data` is only replaced when a video frame is received.
+    pub fn capture_video(
+        &self,
+        video_data: &mut Option<VideoData>,
+        timeout_ms: u32,
+    ) -> Result<FrameType, Error> {
         unsafe {
-            let mut video = if let Some(x) = video_data {
-                mem::MaybeUninit::new(x.p_instance)
-            } else {
-                mem::MaybeUninit::zeroed()
-            };
-
+            let mut video = mem::MaybeUninit::zeroed();
             let response = NDIlib_recv_capture_v3(
                 **self.p_instance,
                 video.as_mut_ptr(),
@@ -356,25 +559,27 @@ impl Recv {
                 timeout_ms,
             );
 
-            if !video.as_ptr().is_null() {
+            let frame_type = FrameType::from_capture(response)?;
+            if frame_type == FrameType::Video {
                 *video_data = Some(VideoData::from_binding_recv(
                     self.p_instance.clone(),
                     video.assume_init(),
                 ));
             }
-
-            FrameType::try_from(response).unwrap()
+            Ok(frame_type)
         }
     }
 
     /// Receive audio frame
-    pub fn capture_audio(&self, audio_data: &mut Option<AudioData>, timeout_ms: u32) -> FrameType {
+    ///
+    /// `audio_data` is only replaced when an audio frame is received.
+    pub fn capture_audio(
+        &self,
+        audio_data: &mut Option<AudioData>,
+        timeout_ms: u32,
+    ) -> Result<FrameType, Error> {
         unsafe {
-            let mut audio = if let Some(x) = audio_data {
-                mem::MaybeUninit::new(x.p_instance)
-            } else {
-                mem::MaybeUninit::zeroed()
-            };
+            let mut audio = mem::MaybeUninit::zeroed();
             let response = NDIlib_recv_capture_v3(
                 **self.p_instance,
                 null_mut(),
@@ -383,24 +588,27 @@ impl Recv {
                 timeout_ms,
             );
 
-            if !audio.as_ptr().is_null() {
+            let frame_type = FrameType::from_capture(response)?;
+            if frame_type == FrameType::Audio {
                 *audio_data = Some(AudioData::from_binding_recv(
                     self.p_instance.clone(),
                     audio.assume_init(),
                 ));
             }
-            FrameType::try_from(response).unwrap()
+            Ok(frame_type)
         }
     }
 
     /// Receive metadata frame
-    pub fn capture_metadata(&self, meta_data: &mut Option<MetaData>, timeout_ms: u32) -> FrameType {
+    ///
+    /// `meta_data` is only replaced when a metadata frame is received.
+    pub fn capture_metadata(
+        &self,
+        meta_data: &mut Option<MetaData>,
+        timeout_ms: u32,
+    ) -> Result<FrameType, Error> {
         unsafe {
-            let mut metadata = if let Some(x) = meta_data {
-                mem::MaybeUninit::new(x.p_instance)
-            } else {
-                mem::MaybeUninit::zeroed()
-            };
+            let mut metadata = mem::MaybeUninit::zeroed();
             let response = NDIlib_recv_capture_v3(
                 **self.p_instance,
                 null_mut(),
@@ -409,13 +617,14 @@ impl Recv {
                 timeout_ms,
             );
 
-            if !metadata.as_ptr().is_null() {
+            let frame_type = FrameType::from_capture(response)?;
+            if frame_type == FrameType::Metadata {
                 *meta_data = Some(MetaData::from_binding_recv(
                     Arc::clone(&self.p_instance),
                     metadata.assume_init(),
                 ));
             }
-            FrameType::try_from(response).unwrap()
+            Ok(frame_type)
         }
     }
 
@@ -491,4 +700,138 @@ impl Recv {
             NDIlib_recv_clear_connection_metadata(**self.p_instance);
         }
     }
+
+    /// Control the PTZ camera connected to this receiver
+    ///
+    /// See [`PtzControl`] for details
+    pub fn ptz(&self) -> PtzControl<'_> {
+        PtzControl::new(self)
+    }
+
+    /// The URL of the web control interface of the connected device, if it has one.
+    ///
+    /// Note that it might take a second or two after the connection for this value to be set.
+    pub fn get_web_control(&self) -> Option<String> {
+        let p_url = {
+            let _lock = self.guard.lock().unwrap();
+            unsafe { NDIlib_recv_get_web_control(**self.p_instance) }
+        };
+        self.take_string(p_url)
+    }
+
+    /// Gather the current settings of the connected device
+    ///
+    /// Call this when [`FrameType::StatusChange`] is captured to find out what changed.
+    pub fn device_status(&self) -> DeviceStatus {
+        DeviceStatus {
+            web_control: self.get_web_control(),
+            ptz_supported: self.ptz().is_supported(),
+            recording_supported: self.recording().is_supported(),
+            no_connections: self.get_no_connections(),
+        }
+    }
+
+    /// Control recording on the source connected to this receiver
+    ///
+    /// See [`RecordingControl`] for details
+    pub fn recording(&self) -> RecordingControl<'_> {
+        RecordingControl::new(self)
+    }
+
+    /// Copy a string allocated by the SDK and free it with `NDIlib_recv_free_string`
+    pub(crate) fn take_string(&self, p_string: *const c_char) -> Option<String> {
+        if p_string.is_null() {
+            return None;
+        }
+
+        let string = unsafe { CStr::from_ptr(p_string) }
+            .to_string_lossy()
+            .to_string();
+        unsafe { NDIlib_recv_free_string(**self.p_instance, p_string) };
+        Some(string)
+    }
+}
+
+// the SDK allows capturing from a receiver on any thread
+pub(crate) struct RecvInstance(pub Arc<OnDrop<NDIlib_recv_instance_t>>);
+
+unsafe impl core::marker::Send for RecvInstance {}
+
+/// Receive the next frame passing `filter` from the receiver `p_instance`, see [`Recv::capture_filtered()`]
+pub(crate) fn capture_frame(
+    p_instance: &Arc<OnDrop<NDIlib_recv_instance_t>>,
+    filter: FrameFilter,
+    timeout_ms: u32,
+) -> Result<Frame, Error> {
+    let mut video = mem::MaybeUninit::<NDIlib_video_frame_v2_t>::zeroed();
+    let mut audio = mem::MaybeUninit::<NDIlib_audio_frame_v3_t>::zeroed();
+    let mut metadata = mem::MaybeUninit::<NDIlib_metadata_frame_t>::zeroed();
+
+    let p_video = if filter.video {
+        video.as_mut_ptr()
+    } else {
+        null_mut()
+    };
+    let p_audio = if filter.audio {
+        audio.as_mut_ptr()
+    } else {
+        null_mut()
+    };
+    let p_metadata = if filter.metadata {
+        metadata.as_mut_ptr()
+    } else {
+        null_mut()
+    };
+    let response =
+        unsafe { NDIlib_recv_capture_v3(***p_instance, p_video, p_audio, p_metadata, timeout_ms) };
+
+    // only the frame of the returned type has been filled in
+    let frame = match FrameType::from_capture(response)? {
+        FrameType::Video => {
+            let video = unsafe { video.assume_init() };
+            Frame::Video(VideoData::from_binding_recv(p_instance.clone(), video))
+        }
+        FrameType::Audio => {
+            let audio = unsafe { audio.assume_init() };
+            Frame::Audio(AudioData::from_binding_recv(p_instance.clone(), audio))
+        }
+        FrameType::Metadata => {
+            let metadata = unsafe { metadata.assume_init() };
+            Frame::Metadata(MetaData::from_binding_recv(p_instance.clone(), metadata))
+        }
+        FrameType::StatusChange => Frame::StatusChange,
+        FrameType::None | FrameType::ErrorFrame => Frame::Timeout,
+    };
+    Ok(frame)
+}
+
+#[test]
+fn device_status_changes() {
+    let previous = DeviceStatus::default();
+    assert!(!previous.changes(&previous).any());
+
+    let current = DeviceStatus {
+        web_control: Some("http://10.28.1.192/configuration/".to_string()),
+        ptz_supported: true,
+        ..previous.clone()
+    };
+    let changes = current.changes(&previous);
+    assert!(changes.any());
+    assert_eq!(
+        changes,
+        DeviceStatusChanges {
+            web_control: true,
+            ptz_supported: true,
+            recording_supported: false,
+            no_connections: false,
+        }
+    );
+}
+
+#[test]
+fn frame_filter_combines() {
+    let filter = FrameFilter::VIDEO | FrameFilter::AUDIO;
+    assert!(filter.video && filter.audio && !filter.metadata);
+    assert_eq!(filter | FrameFilter::METADATA, FrameFilter::ALL);
+    assert_eq!(FrameFilter::NONE | FrameFilter::NONE, FrameFilter::NONE);
 }
@@ -0,0 +1,133 @@
+use super::*;
+use std::ffi::CString;
+
+/// Builder struct for [`Routing`]
+#[derive(Debug, Clone)]
+pub struct RoutingBuilder {
+    ndi_name: Option<String>,
+    groups: Option<String>,
+}
+
+impl RoutingBuilder {
+    /// Create new builder instance
+    pub fn new() -> Self {
+        Self {
+            ndi_name: None,
+            groups: None,
+        }
+    }
+
+    /// This is the name of the NDI source to create.
+    ///
+    /// This will be the name of the NDI source on the network.
+    /// For instance, if your network machine name is called “MyMachine” and you
+    /// specify this parameter as “Program”, the NDI source on the network would be “MyMachine (Program)”.
+    pub fn ndi_name(mut self, ndi_name: String) -> Self {
+        self.ndi_name = Some(ndi_name);
+        self
+    }
+
+    /// Specify the groups that this NDI source should place itself into.
+    ///
+    /// Groups are sets of NDI sources. Any source can be part of any
+    /// number of groups, and groups are comma-separated. For instance
+    /// "cameras,studio 1,10am show" would place a source in the three groups named.
+    pub fn groups(mut self, groups: String) -> Self {
+        self.groups = Some(groups);
+        self
+    }
+
+    /// Build the [`Routing`] instance
+    pub fn build(self, ndi: &Ndi) -> Result<Routing, Error> {
+        let mut settings = NDIlib_routing_create_t {
+            p_ndi_name: null(),
+            p_groups: null(),
+        };
+
+        let cstr_ndi_name: CString;
+        let cstr_ndi_group: CString;
+
+        if let Some(ndi_name) = self.ndi_name {
+            cstr_ndi_name = CString::new(ndi_name).map_err(|_| Error::InvalidString("ndi_name"))?;
+            settings.p_ndi_name = cstr_ndi_name.as_ptr();
+        }
+
+        if let Some(groups) = self.groups {
+            cstr_ndi_group = CString::new(groups).map_err(|_| Error::InvalidString("groups"))?;
+            settings.p_groups = cstr_ndi_group.as_ptr();
+        }
+
+        Routing::with_settings(settings, ndi)
+    }
+}
+
+impl Default for RoutingBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A virtual NDI source which routes receivers to another [`Source`]
+///
+/// Receivers connecting to this source are transparently redirected to whichever
+/// upstream source it currently points at, without the video being decoded or re-encoded.
+/// This is useful for publishing a fixed source name for a switcher output.
+pub struct Routing {
+    p_instance: Arc<OnDrop<NDIlib_routing_instance_t>>,
+}
+
+unsafe impl core::marker::Send for Routing {}
+unsafe impl core::marker::Sync for Routing {}
+
+impl Routing {
+    /// Create a new instance with default parameters
+    ///
+    /// It is recommended to use [`RoutingBuilder`] instead
+    pub fn new(ndi: &Ndi) -> Result<Self, Error> {
+        let settings = NDIlib_routing_create_t {
+            p_ndi_name: null(),
+            p_groups: null(),
+        };
+        Self::with_settings(settings, ndi)
+    }
+
+    fn with_settings(settings: NDIlib_routing_create_t, ndi: &Ndi) -> Result<Self, Error> {
+        let p_instance = unsafe { NDIlib_routing_create(&settings) };
+
+        if p_instance.is_null() {
+            return Err(Error::CreateFailed("Routing"));
+        }
+
+        Ok(Self {
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_routing_destroy(s) }).retain(ndi),
+            ),
+        })
+    }
+
+    /// Change the routing of this source to another source
+    ///
+    /// The return value is whether the routing was changed successfully
+    pub fn route_to(&self, source: &Source) -> bool {
+        unsafe { NDIlib_routing_change(**self.p_instance, &source.to_binding()) }
+    }
+
+    /// Stop routing this source to anything
+    ///
+    /// The return value is whether the routing was cleared successfully
+    pub fn clear(&self) -> bool {
+        unsafe { NDIlib_routing_clear(**self.p_instance) }
+    }
+
+    /// Get the current number of receivers connected to this source.
+    ///
+    /// If you specify a timeout that is not 0 then it will wait until there are connections for this amount of time.
+    pub fn get_no_connections(&self, timeout_ms: u32) -> u32 {
+        unsafe { NDIlib_routing_get_no_connections(**self.p_instance, timeout_ms) as _ }
+    }
+
+    /// Retrieve the source information for this routing instance.
+    pub fn get_source(&self) -> Source {
+        Source::from_binding(unsafe { &*NDIlib_routing_get_source_name(**self.p_instance) })
+    }
+}
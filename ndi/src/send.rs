@@ -1,5 +1,5 @@
 use super::*;
-use std::{convert::TryFrom, ffi::CString, mem::MaybeUninit};
+use std::{ffi::CString, mem::MaybeUninit, sync::Mutex};
 
 /// Builder struct for [`Send`]
 #[derive(Debug, Clone)]
@@ -8,6 +8,7 @@ pub struct SendBuilder {
     groups: Option<String>,
     clock_video: Option<bool>,
     clock_audio: Option<bool>,
+    failover: Option<Source>,
 }
 
 impl SendBuilder {
@@ -18,6 +19,7 @@ impl SendBuilder {
             groups: None,
             clock_video: None,
             clock_audio: None,
+            failover: None,
         }
     }
 
@@ -69,25 +71,35 @@ impl SendBuilder {
         self
     }
 
+    /// Specify a fail-over source for this sender.
+    ///
+    /// If this sender fails, any receivers will automatically switch over to
+    /// the fail-over source, unless this sender comes back online.
+    /// See [`Send::set_failover()`]
+    pub fn failover(mut self, failover: Source) -> Self {
+        self.failover = Some(failover);
+        self
+    }
+
     /// Build the [`Send`] instance
-    pub fn build(self) -> Result<Send, SendCreateError> {
+    pub fn build(self, ndi: &Ndi) -> Result<Send, Error> {
         let mut settings = NDIlib_send_create_t {
             p_ndi_name: null(),
             p_groups: null(),
             clock_video: true,
             clock_audio: true,
         };
-        
+
         let cstr_ndi_name: CString;
         let cstr_ndi_group: CString;
 
         if let Some(ndi_name) = self.ndi_name {
-            cstr_ndi_name = CString::new(ndi_name).unwrap();
+            cstr_ndi_name = CString::new(ndi_name).map_err(|_| Error::InvalidString("ndi_name"))?;
             settings.p_ndi_name = cstr_ndi_name.as_ptr();
         }
 
         if let Some(groups) = self.groups {
-            cstr_ndi_group = CString::new(groups).unwrap();
+            cstr_ndi_group = CString::new(groups).map_err(|_| Error::InvalidString("groups"))?;
             settings.p_groups = cstr_ndi_group.as_ptr();
         }
 
@@ -99,44 +111,91 @@ impl SendBuilder {
             settings.clock_audio = clock_audio;
         }
 
-        Send::with_settings(settings)
+        let send = Send::with_settings(settings, ndi)?;
+        if let Some(failover) = self.failover {
+            send.set_failover(Some(&failover));
+        }
+
+        Ok(send)
+    }
+}
+
+/// A fail-over [`Source`] kept by the [`Send`],
+/// so that the pointers passed to the SDK stay valid for as long as the fail-over is set.
+struct Failover {
+    p_instance: NDIlib_source_t,
+    _source: Source,
+}
+
+impl Failover {
+    fn new(source: &Source) -> Self {
+        // the strings do not move with the source
+        let source = source.clone();
+        Self {
+            p_instance: source.to_binding(),
+            _source: source,
+        }
     }
 }
 
 /// A sender struct for sending NDI
+///
+/// The sender can be shared between threads. Video, audio and metadata can be sent
+/// concurrently from separate threads, as the SDK allows.
+/// [`Send::get_tally()`] and [`Send::get_no_connections()`] are serialized with each other,
+/// and [`Send::get_source()`], the connection metadata calls and [`Send::set_failover()`]
+/// are serialized with each other, so a timed wait never blocks the metadata calls.
 pub struct Send {
     p_instance: Arc<OnDrop<NDIlib_send_instance_t>>,
+    failover: Mutex<Option<Failover>>,
+    // the frame last passed to `send_video_async`, until the SDK is done with it
+    async_video: Mutex<Option<OwnedVideoFrame>>,
+    // serializes the source, connection metadata and fail-over calls, never held while waiting
+    guard: Mutex<()>,
+    // serializes the tally and connection queries, which wait up to their timeout
+    query_guard: Mutex<()>,
 }
 
+unsafe impl core::marker::Send for Send {}
+unsafe impl core::marker::Sync for Send {}
+
 impl Send {
     /// Create a new instance with default parameters
     ///
     /// It is recommended to use [`SendBuilder`] instead
-    pub fn new() -> Result<Self, SendCreateError> {
+    pub fn new(ndi: &Ndi) -> Result<Self, Error> {
         let p_instance = unsafe { NDIlib_send_create(null()) };
 
         if p_instance.is_null() {
-            return Err(SendCreateError);
+            return Err(Error::CreateFailed("Send"));
         }
 
         Ok(Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_send_destroy(s)
-            })),
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_send_destroy(s) }).retain(ndi),
+            ),
+            failover: Mutex::new(None),
+            async_video: Mutex::new(None),
+            guard: Mutex::new(()),
+            query_guard: Mutex::new(()),
         })
     }
 
-    fn with_settings(settings: NDIlib_send_create_t) -> Result<Self, SendCreateError> {
+    fn with_settings(settings: NDIlib_send_create_t, ndi: &Ndi) -> Result<Self, Error> {
         let p_instance = unsafe { NDIlib_send_create(&settings) };
 
         if p_instance.is_null() {
-            return Err(SendCreateError);
+            return Err(Error::CreateFailed("Send"));
         }
 
         Ok(Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_send_destroy(s)
-            })),
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_send_destroy(s) }).retain(ndi),
+            ),
+            failover: Mutex::new(None),
+            async_video: Mutex::new(None),
+            guard: Mutex::new(()),
+            query_guard: Mutex::new(()),
         })
     }
 
@@ -144,40 +203,42 @@ impl Send {
     ///
     /// the return value is whether Tally was actually updated or not
     pub fn get_tally(&self, tally: &mut Tally, timeout_ms: u32) -> bool {
-        unsafe {
-            let p_tally = *tally;
-            let is_updated =
-                NDIlib_send_get_tally(**self.p_instance, &mut p_tally.into(), timeout_ms);
-            is_updated
-        }
+        let _lock = self.query_guard.lock().unwrap();
+        let mut p_tally = (*tally).into();
+        let is_updated =
+            unsafe { NDIlib_send_get_tally(**self.p_instance, &mut p_tally, timeout_ms) };
+        *tally = Tally::from(p_tally);
+        is_updated
     }
 
     /// This allows you to receive metadata from the other end of the connection
-    pub fn capture(&self, meta_data: &mut Option<MetaData>, timeout_ms: u32) -> FrameType {
+    ///
+    /// `meta_data` is only replaced when a metadata frame is received.
+    pub fn capture(
+        &self,
+        meta_data: &mut Option<MetaData>,
+        timeout_ms: u32,
+    ) -> Result<FrameType, Error> {
         unsafe {
-            let mut p_meta = if let Some(metadata) = meta_data {
-                MaybeUninit::new(metadata.p_instance)
-            } else {
-                MaybeUninit::uninit()
-            };
+            let mut p_meta = MaybeUninit::zeroed();
             let frametype = NDIlib_send_capture(**self.p_instance, p_meta.as_mut_ptr(), timeout_ms);
 
-            *meta_data = Some(MetaData::from_binding_send(
-                Arc::clone(&self.p_instance),
-                p_meta.assume_init(),
-            ));
-
-            let res: FrameType = FrameType::try_from(frametype).unwrap();
-
-            res
+            // the frame is only filled in when metadata was received
+            let frame_type = FrameType::from_capture(frametype)?;
+            if frame_type == FrameType::Metadata {
+                *meta_data = Some(MetaData::from_binding_send(
+                    Arc::clone(&self.p_instance),
+                    p_meta.assume_init(),
+                ));
+            }
+            Ok(frame_type)
         }
     }
 
     /// Retrieve the source information for the given sender instance.
     pub fn get_source(&self) -> Source {
-        let instance = unsafe { *NDIlib_send_get_source_name(**self.p_instance) };
-        let parent = SourceParent::Send(Arc::clone(&self.p_instance));
-        Source::from_binding(parent, instance)
+        let _lock = self.guard.lock().unwrap();
+        Source::from_binding(unsafe { &*NDIlib_send_get_source_name(**self.p_instance) })
     }
 
     /// This will add a metadata frame
@@ -188,17 +249,104 @@ impl Send {
     }
 
     /// This will add an audio frame
-    pub fn send_audio(&self, audio_data: &AudioData) {
+    ///
+    /// This accepts frames created for sending as well as received [`AudioData`].
+    pub fn send_audio<F: AsAudioFrame + ?Sized>(&self, audio_data: &F) {
+        unsafe {
+            NDIlib_send_send_audio_v3(**self.p_instance, audio_data.as_audio_frame().as_binding());
+        }
+    }
+
+    /// This will add an audio frame of interleaved 16bpp samples
+    ///
+    /// `data` holds `no_samples * no_channels` samples.
+    /// `reference_level` specifies how many dB above the reference level (+4dBU) the full range of 16 bit audio is.
+    /// Most common applications produce audio at reference level, so +0dB is a good default for sending.
+    /// Use [`NDIlib_send_timecode_synthesize`] as `timecode` to have the SDK generate it.
+    pub fn send_audio_interleaved_16s(
+        &self,
+        data: &[i16],
+        sample_rate: u32,
+        no_channels: u32,
+        reference_level: i32,
+        timecode: i64,
+    ) -> Result<(), Error> {
+        let no_samples = interleaved_no_samples(data.len(), no_channels)?;
+        let frame = NDIlib_audio_frame_interleaved_16s_t {
+            sample_rate: sample_rate as _,
+            no_channels: no_channels as _,
+            no_samples: no_samples as _,
+            timecode,
+            reference_level,
+            p_data: data.as_ptr() as _,
+        };
+        unsafe {
+            NDIlib_util_send_send_audio_interleaved_16s(**self.p_instance, &frame);
+        }
+        Ok(())
+    }
+
+    /// This will add an audio frame of interleaved 32bpp samples
+    ///
+    /// See [`Send::send_audio_interleaved_16s()`] for the meaning of the parameters.
+    pub fn send_audio_interleaved_32s(
+        &self,
+        data: &[i32],
+        sample_rate: u32,
+        no_channels: u32,
+        reference_level: i32,
+        timecode: i64,
+    ) -> Result<(), Error> {
+        let no_samples = interleaved_no_samples(data.len(), no_channels)?;
+        let frame = NDIlib_audio_frame_interleaved_32s_t {
+            sample_rate: sample_rate as _,
+            no_channels: no_channels as _,
+            no_samples: no_samples as _,
+            timecode,
+            reference_level,
+            p_data: data.as_ptr() as _,
+        };
         unsafe {
-            NDIlib_send_send_audio_v3(**self.p_instance, &audio_data.p_instance);
+            NDIlib_util_send_send_audio_interleaved_32s(**self.p_instance, &frame);
         }
+        Ok(())
+    }
+
+    /// This will add an audio frame of interleaved floating point samples
+    ///
+    /// The -1.0 to +1.0 range corresponds to the professional audio reference level of +4dBU.
+    /// See [`Send::send_audio_interleaved_16s()`] for the meaning of the other parameters.
+    pub fn send_audio_interleaved_32f(
+        &self,
+        data: &[f32],
+        sample_rate: u32,
+        no_channels: u32,
+        timecode: i64,
+    ) -> Result<(), Error> {
+        let no_samples = interleaved_no_samples(data.len(), no_channels)?;
+        let frame = NDIlib_audio_frame_interleaved_32f_t {
+            sample_rate: sample_rate as _,
+            no_channels: no_channels as _,
+            no_samples: no_samples as _,
+            timecode,
+            p_data: data.as_ptr() as _,
+        };
+        unsafe {
+            NDIlib_util_send_send_audio_interleaved_32f(**self.p_instance, &frame);
+        }
+        Ok(())
     }
 
     /// This will add a video frame
-    pub fn send_video(&self, video_data: &VideoData) {
+    ///
+    /// This accepts frames created for sending as well as received [`VideoData`].
+    pub fn send_video<F: AsVideoFrame + ?Sized>(&self, video_data: &F) {
+        let mut async_video = self.async_video.lock().unwrap();
         unsafe {
-            NDIlib_send_send_video_v2(**self.p_instance, &video_data.p_instance);
+            NDIlib_send_send_video_v2(**self.p_instance, video_data.as_video_frame().as_binding());
         }
+        // this is a synchronizing event for the last asynchronous frame
+        *async_video = None;
     }
 
     /// This will add a video frame and will return immediately, having scheduled the frame to be displayed.
@@ -214,12 +362,19 @@ impl Send {
     /// Synchronizing events are :
     /// - a call to `send_video`
     /// - a call to `send_video_async` with another frame to be sent
-    /// - a call to `send_video` with p_video_data=NULL
     /// - Dropping a [`Send`] instance
-    pub fn send_video_async(&self, video_data: &VideoData) {
+    ///
+    /// The frame is kept by the [`Send`] until the next synchronizing event,
+    /// and the previous frame is given back so its buffer can be reused for the next frame.
+    pub fn send_video_async(&self, video_data: OwnedVideoFrame) -> Option<OwnedVideoFrame> {
+        let mut async_video = self.async_video.lock().unwrap();
         unsafe {
-            NDIlib_send_send_video_async_v2(**self.p_instance, &video_data.p_instance);
+            NDIlib_send_send_video_async_v2(
+                **self.p_instance,
+                video_data.as_video_frame().as_binding(),
+            );
         }
+        async_video.replace(video_data)
     }
 
     /// Get the current number of receivers connected to this source.
@@ -228,9 +383,51 @@ impl Send {
     /// which can significantly improve the efficiency if you want to make a lot of sources available on the network.
     /// If you specify a timeout that is not 0 then it will wait until there are connections for this amount of time.
     pub fn get_no_connections(&self, timeout_ms: u32) -> u32 {
+        let _lock = self.query_guard.lock().unwrap();
         unsafe { NDIlib_send_get_no_connections(**self.p_instance, timeout_ms) as _ }
     }
 
+    /// Add a connection metadata string to the list of what is sent on each new connection.
+    ///
+    /// If someone is already connected then this string will be sent to them immediately.
+    /// Connection based metadata is data that is sent automatically each time a new connection is received.
+    /// To reset them you need to clear them all and set them up again using [`Send::clear_connection_metadata()`].
+    /// See [`CapabilitiesBuilder`] and [`ProductBuilder`] for the standard announcements.
+    pub fn add_connection_metadata(&self, metadata: &MetaData) {
+        let _lock = self.guard.lock().unwrap();
+        unsafe {
+            NDIlib_send_add_connection_metadata(**self.p_instance, &metadata.p_instance);
+        }
+    }
+
+    /// Clear all connection metadata
+    pub fn clear_connection_metadata(&self) {
+        let _lock = self.guard.lock().unwrap();
+        unsafe {
+            NDIlib_send_clear_connection_metadata(**self.p_instance);
+        }
+    }
+
+    /// Assign a new fail-over source for this sender.
+    ///
+    /// If this sender was to fail, any receivers would automatically switch over to
+    /// use the fail-over source, unless this sender then came back online.
+    /// Specify `None` to clear the fail-over source.
+    pub fn set_failover(&self, source: Option<&Source>) {
+        let _lock = self.guard.lock().unwrap();
+        let mut current = self.failover.lock().unwrap();
+        let failover = source.map(Failover::new);
+        let p_failover = failover
+            .as_ref()
+            .map_or(null(), |f| &f.p_instance as *const NDIlib_source_t);
+        unsafe {
+            NDIlib_send_set_failover(**self.p_instance, p_failover);
+        }
+
+        // the previous fail-over is only dropped once the SDK has been given the new one
+        *current = failover;
+    }
+
     // Free the buffers returned by capture for metadata
     // pub(crate) fn free_metadata(&self, metadata: &mut MetaData) {
     //     unsafe {
@@ -238,3 +435,29 @@ impl Send {
     //     }
     // }
 }
+
+impl Drop for Send {
+    fn drop(&mut self) {
+        let pending = self.async_video.get_mut().map_or(true, |v| v.is_some());
+        if pending {
+            // wait for the SDK to be done with the frame before it is freed
+            unsafe { NDIlib_send_send_video_async_v2(**self.p_instance, null()) };
+        }
+    }
+}
+
+/// The number of samples per channel in an interleaved buffer of `len` samples
+fn interleaved_no_samples(len: usize, no_channels: u32) -> Result<usize, Error> {
+    let no_channels = no_channels as usize;
+    if no_channels == 0 || len / no_channels * no_channels != len {
+        return Err(Error::InvalidBufferSize(len, "a multiple of no_channels"));
+    }
+
+    Ok(len / no_channels)
+}
+
+#[test]
+fn send_is_thread_safe() {
+    fn assert_thread_safe<T: core::marker::Send + Sync>() {}
+    assert_thread_safe::<Send>();
+}
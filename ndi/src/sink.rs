@@ -0,0 +1,133 @@
+use super::*;
+use futures::{
+    channel::{mpsc, oneshot},
+    executor::block_on,
+    ready, Sink, StreamExt,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    thread,
+};
+
+/// A frame sent through a [`SendSink`]
+pub enum OutgoingFrame {
+    /// A video frame, sent with [`Send::send_video_async()`]
+    Video(OwnedVideoFrame),
+    /// An audio frame, sent with [`Send::send_audio()`]
+    Audio(OwnedAudioFrame),
+    /// A metadata frame, sent with [`Send::send_metadata()`]
+    Metadata(MetaData),
+}
+
+impl From<OwnedVideoFrame> for OutgoingFrame {
+    fn from(frame: OwnedVideoFrame) -> Self {
+        OutgoingFrame::Video(frame)
+    }
+}
+
+impl From<OwnedAudioFrame> for OutgoingFrame {
+    fn from(frame: OwnedAudioFrame) -> Self {
+        OutgoingFrame::Audio(frame)
+    }
+}
+
+impl From<MetaData> for OutgoingFrame {
+    fn from(frame: MetaData) -> Self {
+        OutgoingFrame::Metadata(frame)
+    }
+}
+
+/// An async [`Sink`] sending frames through a [`Send`], see [`Send::into_sink()`]
+///
+/// The frames are submitted on a dedicated thread, so clocked sends never block the executor,
+/// and the sink only accepts the next frame once that thread is ready for it.
+/// Video frames are kept alive until the SDK is done with them.
+/// Flushing only waits for the frames to be handed to the thread,
+/// closing also waits for all of them to be sent and released.
+pub struct SendSink {
+    frames: mpsc::Sender<OutgoingFrame>,
+    done: oneshot::Receiver<()>,
+}
+
+impl SendSink {
+    fn new(send: Send) -> Self {
+        // a frame can wait while the previous one is sent
+        let (frames, mut outgoing) = mpsc::channel(0);
+        let (finished, done) = oneshot::channel();
+
+        thread::spawn(move || {
+            while let Some(frame) = block_on(outgoing.next()) {
+                match frame {
+                    // the previous video frame is released once the SDK moved on to this one
+                    OutgoingFrame::Video(frame) => drop(send.send_video_async(frame)),
+                    OutgoingFrame::Audio(frame) => send.send_audio(&frame),
+                    OutgoingFrame::Metadata(frame) => send.send_metadata(&frame),
+                }
+            }
+
+            // waits for the SDK to release the last video frame
+            drop(send);
+            let _ = finished.send(());
+        });
+
+        Self { frames, done }
+    }
+}
+
+impl Sink<OutgoingFrame> for SendSink {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.frames)
+            .poll_ready(cx)
+            .map_err(|_| Error::Disconnected)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, frame: OutgoingFrame) -> Result<(), Error> {
+        Pin::new(&mut self.frames)
+            .start_send(frame)
+            .map_err(|_| Error::Disconnected)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.frames)
+            .poll_flush(cx)
+            .map_err(|_| Error::Disconnected)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        ready!(Pin::new(&mut self.frames).poll_close(cx)).map_err(|_| Error::Disconnected)?;
+        // the thread exits once it has sent every frame
+        Pin::new(&mut self.done).poll(cx).map(|_| Ok(()))
+    }
+}
+
+impl Send {
+    /// Turn the sender into a [`Sink`] of [`OutgoingFrame`]s for async code
+    pub fn into_sink(self) -> SendSink {
+        SendSink::new(self)
+    }
+}
+
+#[test]
+fn send_sink_is_send() {
+    fn assert_send<T: core::marker::Send + Unpin>() {}
+    assert_send::<SendSink>();
+    assert_send::<OutgoingFrame>();
+}
+
+#[test]
+fn outgoing_metadata_owns_its_data() {
+    let metadata = MetaData::new(0, 0, "<ndi_tally on_program=\"true\"/>".to_string()).unwrap();
+    let frame = OutgoingFrame::from(metadata);
+    match &frame {
+        OutgoingFrame::Metadata(metadata) => {
+            assert_eq!(metadata.data(), "<ndi_tally on_program=\"true\"/>")
+        }
+        _ => panic!("expected a metadata frame"),
+    }
+    // frees the string
+    drop(frame);
+}
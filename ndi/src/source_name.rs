@@ -0,0 +1,142 @@
+use super::*;
+use std::str::FromStr;
+
+/// The name of a [`Source`], in the form `MACHINE_NAME (NDI_SOURCE_NAME)`
+///
+/// Names sort and compare by machine name first, then by stream name.
+/// Stream names may contain parentheses themselves, such as `MACHINE (Camera (2))`,
+/// machine names cannot.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceName {
+    machine: String,
+    stream: String,
+}
+
+impl SourceName {
+    /// Create a name from its machine and stream parts
+    pub fn new(machine: &str, stream: &str) -> Self {
+        Self {
+            machine: machine.to_string(),
+            stream: stream.to_string(),
+        }
+    }
+
+    /// The name of the machine the source runs on
+    pub fn machine(&self) -> &str {
+        &self.machine
+    }
+
+    /// The name of the source on its machine
+    pub fn stream(&self) -> &str {
+        &self.stream
+    }
+
+    /// Whether `name` refers to this source
+    ///
+    /// A full name must match exactly, while a name without a machine part,
+    /// such as `Camera (2)`, matches the stream on any machine.
+    pub fn refers_to(&self, name: &str) -> bool {
+        match name.parse::<SourceName>() {
+            Ok(full) if *self == full => true,
+            _ => self.stream == name,
+        }
+    }
+
+    /// Whether the full name matches a glob `pattern`
+    ///
+    /// `*` matches any sequence of characters and `?` matches any single character.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let name: Vec<char> = self.to_string().chars().collect();
+        let pattern: Vec<char> = pattern.chars().collect();
+        glob_matches(&pattern, &name)
+    }
+}
+
+impl FromStr for SourceName {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidSourceName(name.to_string());
+
+        // the machine name ends at the first parenthesis, the stream name at the last one
+        let open = name.find('(').ok_or_else(invalid)?;
+        if !name.ends_with(')') || open == 0 {
+            return Err(invalid());
+        }
+
+        let machine = name[..open].trim_end();
+        let stream = &name[open + 1..name.len() - 1];
+        if machine.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self::new(machine, stream))
+    }
+}
+
+impl Display for SourceName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.machine, self.stream)
+    }
+}
+
+impl Source {
+    /// The parsed name of this source
+    ///
+    /// Returns `None` for sources without a name in the usual form, such as those created
+    /// with [`Source::from_address()`].
+    pub fn source_name(&self) -> Option<SourceName> {
+        self.get_name().parse().ok()
+    }
+}
+
+// iterative, backtracking only to the last `*`, so it runs in O(pattern * name)
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // the position after the last `*`, and the position in the name it matched up to
+    let mut star = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p + 1, n));
+            p += 1;
+        } else if let Some((after_star, matched)) = star {
+            // let the last `*` match one more character
+            p = after_star;
+            n = matched + 1;
+            star = Some((after_star, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[test]
+fn source_names() {
+    let name: SourceName = "MACHINE (Camera (2))".parse().unwrap();
+    assert_eq!(name.machine(), "MACHINE");
+    assert_eq!(name.stream(), "Camera (2)");
+    assert_eq!(name.to_string(), "MACHINE (Camera (2))");
+
+    assert!(name.refers_to("MACHINE (Camera (2))"));
+    assert!(name.refers_to("Camera (2)"));
+    assert!(!name.refers_to("OTHER (Camera (2))"));
+
+    assert!(name.matches("MACHINE (Camera*)"));
+    assert!(name.matches("*(Camera (?))"));
+    assert!(!name.matches("*(Camera)"));
+    assert!(name.matches("*"));
+    assert!(!name.matches("MACHINE"));
+
+    let long = SourceName::new(&"a".repeat(100), &"a".repeat(100));
+    assert!(!long.matches("*a*a*a*a*a*a*a*a*a*a*a*a*b"));
+    assert!(long.matches("*a*a*a*a*a*a*a*a*a*a*a*a*)"));
+
+    assert!(SourceName::new("A", "Z") < SourceName::new("B", "A"));
+    assert!("MACHINE".parse::<SourceName>().is_err());
+    assert!("(Camera)".parse::<SourceName>().is_err());
+}
@@ -0,0 +1,164 @@
+use super::*;
+use crate::recv::{capture_frame, RecvInstance};
+use futures::{channel::mpsc, executor::block_on, SinkExt, Stream};
+use std::{
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// An async [`Stream`] of the frames received by a [`Recv`]
+///
+/// The frames are captured on dedicated threads, one for each kind of frame,
+/// which are stopped and joined when the stream is dropped.
+/// Frames back up in the SDK while the stream is not polled.
+/// A lost connection is reported once, until frames are received again.
+pub struct FrameStream {
+    frames: mpsc::Receiver<Result<Frame, Error>>,
+    stop: Arc<AtomicBool>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+/// Which frames a capture thread captures
+#[derive(Clone, Copy)]
+struct Kinds {
+    filter: FrameFilter,
+    status_change: bool,
+}
+
+impl FrameStream {
+    fn spawn(recv: &Recv, threads: &[Kinds]) -> Self {
+        let (sender, frames) = mpsc::channel(1);
+        let stop = Arc::new(AtomicBool::new(false));
+        // shared, so that the threads report a lost connection only once
+        let disconnected = Arc::new(AtomicBool::new(false));
+
+        let workers = threads
+            .iter()
+            .map(|&kinds| {
+                let p_instance = RecvInstance(recv.p_instance.clone());
+                let sender = sender.clone();
+                let stop = stop.clone();
+                let disconnected = disconnected.clone();
+                thread::spawn(move || capture_frames(p_instance, kinds, sender, stop, disconnected))
+            })
+            .collect();
+
+        Self {
+            frames,
+            stop,
+            workers,
+        }
+    }
+}
+
+impl Stream for FrameStream {
+    type Item = Result<Frame, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.frames).poll_next(cx)
+    }
+}
+
+impl Drop for FrameStream {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // unblocks the threads waiting for the stream to be polled
+        self.frames.close();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn capture_frames(
+    RecvInstance(p_instance): RecvInstance,
+    kinds: Kinds,
+    mut frames: mpsc::Sender<Result<Frame, Error>>,
+    stop: Arc<AtomicBool>,
+    disconnected: Arc<AtomicBool>,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        // a short timeout, to notice when to stop
+        let frame = match capture_frame(&p_instance, kinds.filter, 100) {
+            Ok(Frame::StatusChange) if !kinds.status_change => continue,
+            Ok(Frame::Timeout) => continue,
+            Err(Error::Disconnected) => {
+                if disconnected.swap(true, Ordering::Relaxed) {
+                    // already reported, back off until the source is back
+                    thread::sleep(Duration::from_millis(100));
+                    continue;
+                }
+                Err(Error::Disconnected)
+            }
+            frame => {
+                disconnected.store(false, Ordering::Relaxed);
+                frame
+            }
+        };
+
+        if block_on(frames.send(frame)).is_err() {
+            break;
+        }
+    }
+}
+
+const VIDEO: Kinds = Kinds {
+    filter: FrameFilter::VIDEO,
+    status_change: false,
+};
+const AUDIO: Kinds = Kinds {
+    filter: FrameFilter::AUDIO,
+    status_change: false,
+};
+const METADATA: Kinds = Kinds {
+    filter: FrameFilter::METADATA,
+    status_change: false,
+};
+
+impl Recv {
+    /// Turn the receiver into a [`Stream`] of all video, audio, metadata and status change frames
+    ///
+    /// Each kind of frame is captured on its own thread, as the SDK allows.
+    /// Yields [`Error::Disconnected`] once whenever the connection is lost.
+    pub fn into_stream(self) -> FrameStream {
+        // the SDK hands a status change to whichever thread is capturing, so every thread forwards them
+        let threads = [VIDEO, AUDIO, METADATA].map(|kinds| Kinds {
+            status_change: true,
+            ..kinds
+        });
+        FrameStream::spawn(&self, &threads)
+    }
+
+    /// A [`Stream`] of the video frames of this receiver, as [`Frame::Video`]
+    ///
+    /// Video should not be captured in any other way while the stream exists,
+    /// as every frame only goes to one of them.
+    pub fn video_stream(&self) -> FrameStream {
+        FrameStream::spawn(self, &[VIDEO])
+    }
+
+    /// A [`Stream`] of the audio frames of this receiver, as [`Frame::Audio`]
+    ///
+    /// Audio should not be captured in any other way while the stream exists,
+    /// as every frame only goes to one of them.
+    pub fn audio_stream(&self) -> FrameStream {
+        FrameStream::spawn(self, &[AUDIO])
+    }
+
+    /// A [`Stream`] of the metadata frames of this receiver, as [`Frame::Metadata`]
+    ///
+    /// Metadata should not be captured in any other way while the stream exists,
+    /// as every frame only goes to one of them.
+    pub fn metadata_stream(&self) -> FrameStream {
+        FrameStream::spawn(self, &[METADATA])
+    }
+}
+
+#[test]
+fn frame_stream_is_send() {
+    fn assert_send<T: core::marker::Send + Unpin>() {}
+    assert_send::<FrameStream>();
+}
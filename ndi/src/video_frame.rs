@@ -0,0 +1,313 @@
+use super::*;
+use std::{convert::TryFrom, marker::PhantomData};
+
+/// A video frame that can be sent with [`Send::send_video()`]
+///
+/// This is implemented for frames created for sending ([`VideoFrame`], [`OwnedVideoFrame`])
+/// as well as for received frames ([`VideoData`]), which can be forwarded without copying them.
+pub trait AsVideoFrame {
+    /// Borrow this frame as a [`VideoFrame`]
+    fn as_video_frame(&self) -> VideoFrame<'_>;
+}
+
+fn frame_binding(
+    width: u32,
+    height: u32,
+    four_cc: FourCCVideoType,
+    line_stride_in_bytes: u32,
+    data: &[u8],
+) -> Result<NDIlib_video_frame_v2_t, Error> {
+    let xres = i32::try_from(width).map_err(|_| Error::OutOfRange(width as _, "width"))?;
+    let yres = i32::try_from(height).map_err(|_| Error::OutOfRange(height as _, "height"))?;
+    let stride = i32::try_from(line_stride_in_bytes)
+        .map_err(|_| Error::OutOfRange(line_stride_in_bytes as _, "line_stride_in_bytes"))?;
+
+    if width == 0 {
+        return Err(Error::OutOfRange(0.0, "width"));
+    }
+    if height == 0 {
+        return Err(Error::OutOfRange(0.0, "height"));
+    }
+    convert::check_dimensions(four_cc, width as _, height as _)?;
+
+    // the SDK reads whole lines from every line stride
+    if (line_stride_in_bytes as usize) < four_cc.packed_line_stride(width as _) {
+        return Err(Error::InvalidBufferSize(
+            line_stride_in_bytes as _,
+            "a line stride of at least the packed line size of the FourCC",
+        ));
+    }
+    if data.len() < four_cc.buffer_size(line_stride_in_bytes as _, height as _) {
+        return Err(Error::InvalidBufferSize(
+            data.len(),
+            "line_stride_in_bytes * height for the planes of the FourCC",
+        ));
+    }
+
+    // from default c++ constructor in Processing.NDI.structs.h
+    Ok(NDIlib_video_frame_v2_t {
+        xres,
+        yres,
+        FourCC: four_cc as _,
+        frame_rate_N: 30000,
+        frame_rate_D: 1001,
+        picture_aspect_ratio: 0.0,
+        frame_format_type: FrameFormatType::Progressive as _,
+        timecode: NDIlib_send_timecode_synthesize,
+        p_data: data.as_ptr() as _,
+        __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+            line_stride_in_bytes: stride,
+        },
+        p_metadata: null(),
+        timestamp: 0,
+    })
+}
+
+// The settings shared by `VideoFrame` and `OwnedVideoFrame`
+macro_rules! impl_frame_settings {
+    () => {
+        /// Set the framerate as a fraction, for instance 30000/1001 for 29.97 fps
+        ///
+        /// default: 30000/1001
+        pub fn frame_rate(mut self, numerator: i32, denominator: i32) -> Self {
+            self.p_instance.frame_rate_N = numerator;
+            self.p_instance.frame_rate_D = denominator;
+            self
+        }
+
+        /// Set the picture aspect ratio (as opposed to the pixel aspect ratio)
+        ///
+        /// default: 0.0, which is interpreted as width/height, or square pixels
+        pub fn picture_aspect_ratio(mut self, picture_aspect_ratio: f32) -> Self {
+            self.p_instance.picture_aspect_ratio = picture_aspect_ratio;
+            self
+        }
+
+        /// Set whether the frame is progressive or fielded
+        ///
+        /// default: [`FrameFormatType::Progressive`]
+        pub fn frame_format_type(mut self, frame_format_type: FrameFormatType) -> Self {
+            self.p_instance.frame_format_type = frame_format_type as _;
+            self
+        }
+
+        /// Set the timecode of this frame in 100 ns intervals
+        ///
+        /// default: [`NDIlib_send_timecode_synthesize`], which has the SDK generate it
+        pub fn timecode(mut self, timecode: i64) -> Self {
+            self.p_instance.timecode = timecode;
+            self
+        }
+    };
+}
+
+/// A video frame borrowing its pixel data, for sending
+///
+/// The borrow ensures that the buffer outlives the frame.
+/// Use [`OwnedVideoFrame`] if the frame should own its buffer instead.
+#[derive(Clone, Copy)]
+pub struct VideoFrame<'a> {
+    p_instance: NDIlib_video_frame_v2_t,
+    _data: PhantomData<&'a [u8]>,
+}
+
+unsafe impl core::marker::Send for VideoFrame<'_> {}
+unsafe impl core::marker::Sync for VideoFrame<'_> {}
+
+impl<'a> VideoFrame<'a> {
+    /// Create a frame from `data` in the `four_cc` format, with packed lines
+    ///
+    /// Returns [`Error::InvalidBufferSize`] if `data` is too small for the frame.
+    pub fn new(
+        width: u32,
+        height: u32,
+        four_cc: FourCCVideoType,
+        data: &'a [u8],
+    ) -> Result<Self, Error> {
+        let line_stride_in_bytes = four_cc.packed_line_stride(width as _) as _;
+        Self::with_line_stride(width, height, four_cc, line_stride_in_bytes, data)
+    }
+
+    /// Create a frame from `data` in the `four_cc` format with `line_stride_in_bytes` bytes between lines
+    ///
+    /// Returns [`Error::InvalidBufferSize`] if `data` is too small for the frame.
+    pub fn with_line_stride(
+        width: u32,
+        height: u32,
+        four_cc: FourCCVideoType,
+        line_stride_in_bytes: u32,
+        data: &'a [u8],
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            p_instance: frame_binding(width, height, four_cc, line_stride_in_bytes, data)?,
+            _data: PhantomData,
+        })
+    }
+
+    pub(crate) fn from_binding(p_instance: NDIlib_video_frame_v2_t) -> Self {
+        Self {
+            p_instance,
+            _data: PhantomData,
+        }
+    }
+
+    pub(crate) fn as_binding(&self) -> &NDIlib_video_frame_v2_t {
+        &self.p_instance
+    }
+
+    pub(crate) fn four_cc(&self) -> Result<FourCCVideoType, Error> {
+        FourCCVideoType::try_from(self.p_instance.FourCC)
+    }
+
+    /// The line stride of an uncompressed frame, where 0 stands for packed lines
+    pub(crate) fn line_stride_in_bytes(&self, four_cc: FourCCVideoType) -> usize {
+        match unsafe { self.p_instance.__bindgen_anon_1.line_stride_in_bytes } {
+            stride if stride > 0 => stride as _,
+            _ => four_cc.packed_line_stride(self.p_instance.xres as _),
+        }
+    }
+
+    /// The planes of an uncompressed frame, for reading back its pixel data
+    pub(crate) fn planes(&self) -> Result<Vec<Plane<'a>>, Error> {
+        let four_cc = self.four_cc()?;
+        if self.p_instance.p_data.is_null() {
+            return Err(Error::InvalidBufferSize(0, "p_data"));
+        }
+
+        let (width, height) = (self.p_instance.xres as usize, self.p_instance.yres as usize);
+        let line_stride = self.line_stride_in_bytes(four_cc);
+        let data = unsafe {
+            std::slice::from_raw_parts::<'a, u8>(
+                self.p_instance.p_data,
+                four_cc.buffer_size(line_stride, height),
+            )
+        };
+        let layouts = four_cc.plane_layouts(width, height, line_stride);
+        Ok(layouts
+            .iter()
+            .map(|layout| Plane::new(layout, data))
+            .collect())
+    }
+
+    impl_frame_settings!();
+
+    /// Set the per frame metadata, which should be XML
+    pub fn metadata(mut self, metadata: &'a CStr) -> Self {
+        self.p_instance.p_metadata = metadata.as_ptr();
+        self
+    }
+}
+
+impl AsVideoFrame for VideoFrame<'_> {
+    fn as_video_frame(&self) -> VideoFrame<'_> {
+        *self
+    }
+}
+
+/// A video frame owning its pixel data and metadata, for sending
+///
+/// Unlike [`VideoFrame`] this can be passed to [`Send::send_video_async()`],
+/// which keeps it alive for as long as the SDK is using it.
+pub struct OwnedVideoFrame {
+    p_instance: NDIlib_video_frame_v2_t,
+    data: Vec<u8>,
+    metadata: Option<CString>,
+}
+
+unsafe impl core::marker::Send for OwnedVideoFrame {}
+unsafe impl core::marker::Sync for OwnedVideoFrame {}
+
+impl OwnedVideoFrame {
+    /// Create a frame from `data` in the `four_cc` format, with packed lines
+    ///
+    /// Returns [`Error::InvalidBufferSize`] if `data` is too small for the frame.
+    pub fn new(
+        width: u32,
+        height: u32,
+        four_cc: FourCCVideoType,
+        data: Vec<u8>,
+    ) -> Result<Self, Error> {
+        let line_stride_in_bytes = four_cc.packed_line_stride(width as _) as _;
+        Self::with_line_stride(width, height, four_cc, line_stride_in_bytes, data)
+    }
+
+    /// Create a frame from `data` in the `four_cc` format with `line_stride_in_bytes` bytes between lines
+    ///
+    /// Returns [`Error::InvalidBufferSize`] if `data` is too small for the frame.
+    pub fn with_line_stride(
+        width: u32,
+        height: u32,
+        four_cc: FourCCVideoType,
+        line_stride_in_bytes: u32,
+        data: Vec<u8>,
+    ) -> Result<Self, Error> {
+        // the heap allocation of `data` does not move along with the frame
+        Ok(Self {
+            p_instance: frame_binding(width, height, four_cc, line_stride_in_bytes, &data)?,
+            data,
+            metadata: None,
+        })
+    }
+
+    impl_frame_settings!();
+
+    /// Set the per frame metadata, which should be XML
+    pub fn metadata(mut self, metadata: String) -> Result<Self, Error> {
+        let metadata = CString::new(metadata).map_err(|_| Error::InvalidString("metadata"))?;
+        self.p_instance.p_metadata = metadata.as_ptr();
+        self.metadata = Some(metadata);
+        Ok(self)
+    }
+
+    /// The pixel data of the frame
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The pixel data of the frame, to render the next frame into it
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    /// Take back the pixel data of the frame
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl AsVideoFrame for OwnedVideoFrame {
+    fn as_video_frame(&self) -> VideoFrame<'_> {
+        VideoFrame::from_binding(self.p_instance)
+    }
+}
+
+impl AsVideoFrame for VideoData {
+    fn as_video_frame(&self) -> VideoFrame<'_> {
+        VideoFrame::from_binding(self.p_instance)
+    }
+}
+
+#[test]
+fn video_frame_buffer_size() {
+    let data = vec![0; 4 * 16 * 9];
+    assert!(VideoFrame::new(16, 9, FourCCVideoType::BGRA, &data).is_ok());
+    assert!(VideoFrame::new(16, 10, FourCCVideoType::BGRA, &data).is_err());
+    assert!(VideoFrame::with_line_stride(16, 9, FourCCVideoType::BGRA, 4 * 17, &data).is_err());
+    assert_eq!(
+        VideoFrame::with_line_stride(16, 9, FourCCVideoType::BGRA, 4, &[0; 36]).err(),
+        Some(Error::InvalidBufferSize(
+            4,
+            "a line stride of at least the packed line size of the FourCC"
+        ))
+    );
+    assert!(VideoFrame::new(0, 9, FourCCVideoType::BGRA, &data).is_err());
+    assert!(VideoFrame::new(15, 9, FourCCVideoType::UYVY, &data).is_err());
+
+    let frame = OwnedVideoFrame::new(16, 8, FourCCVideoType::NV12, vec![0; 16 * 12])
+        .unwrap()
+        .frame_rate(60, 1);
+    let binding = *frame.as_video_frame().as_binding();
+    assert_eq!(binding.frame_rate_N, 60);
+    assert_eq!(binding.p_data as *const u8, frame.data().as_ptr());
+    assert!(OwnedVideoFrame::new(16, 8, FourCCVideoType::NV12, vec![0; 16 * 8]).is_err());
+}